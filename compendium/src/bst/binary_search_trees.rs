@@ -2,14 +2,27 @@ use crate::utilities::MinMax;
 use std::fmt::Debug;
 use std::ops::Add;
 
+/// Data of a node computed from its key and the data of its children, such as the size or the
+/// maximum of the subtree. The BST keeps it up to date along `add` and `delete`.
+pub trait Augmentation<T>: Clone {
+    fn compute(key: &T, left: Option<&Self>, right: Option<&Self>) -> Self;
+}
+
+/// No augmentation, the default of a BST.
+impl<T> Augmentation<T> for () {
+    fn compute(_: &T, _: Option<&()>, _: Option<&()>) {}
+}
+
 #[derive(Clone)]
-pub struct BST<T>
+pub struct BST<T, A = ()>
 where
     T: Default + Ord + Debug + Copy + Clone,
+    A: Augmentation<T>,
 {
     key: T,
-    left: Option<Box<BST<T>>>,
-    right: Option<Box<BST<T>>>,
+    augmentation: A,
+    left: Option<Box<BST<T, A>>>,
+    right: Option<Box<BST<T, A>>>,
 }
 
 impl<T> BST<T>
@@ -25,11 +38,7 @@ where
     /// returns: BST<T>
     ///
     pub fn with_root(key: T) -> Self {
-        Self {
-            key,
-            left: None,
-            right: None,
-        }
+        Self::with_augmented_root(key)
     }
 
     pub fn from_vec(v: Vec<T>) -> Self {
@@ -39,6 +48,54 @@ where
         }
         bst
     }
+}
+
+impl<T, A> BST<T, A>
+where
+    T: Default + Ord + Debug + MinMax + Copy + Clone,
+    A: Augmentation<T>,
+{
+    /// Creates a new BST<T, A> with the given key, computing its augmentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: the key of the BST<T, A>
+    ///
+    /// returns: BST<T, A>
+    ///
+    pub fn with_augmented_root(key: T) -> Self {
+        Self {
+            key,
+            augmentation: A::compute(&key, None, None),
+            left: None,
+            right: None,
+        }
+    }
+
+    pub fn key(&self) -> T {
+        self.key
+    }
+
+    pub fn augmentation(&self) -> &A {
+        &self.augmentation
+    }
+
+    pub fn left(&self) -> Option<&Self> {
+        self.left.as_deref()
+    }
+
+    pub fn right(&self) -> Option<&Self> {
+        self.right.as_deref()
+    }
+
+    /// Recomputes the augmentation of the node from the ones of its children.
+    fn update_augmentation(&mut self) {
+        self.augmentation = A::compute(
+            &self.key,
+            self.left.as_ref().map(|left| &left.augmentation),
+            self.right.as_ref().map(|right| &right.augmentation),
+        );
+    }
 
     pub fn print(&self) {
         println!("{:?}", self.key);
@@ -63,15 +120,16 @@ where
             if let Some(ref mut left) = self.left {
                 left.add(val);
             } else {
-                self.left = Some(Box::new(BST::with_root(val)));
+                self.left = Some(Box::new(BST::with_augmented_root(val)));
             }
         } else {
             if let Some(ref mut right) = self.right {
                 right.add(val);
             } else {
-                self.right = Some(Box::new(BST::with_root(val)));
+                self.right = Some(Box::new(BST::with_augmented_root(val)));
             }
         }
+        self.update_augmentation();
     }
 
     pub fn min(&self) -> &Self {
@@ -92,50 +150,57 @@ where
         if !self.search(x) {
             return false;
         }
-        match self.delete_rec(x) {
-            None => false,
-            Some(_) => true,
-        }
+        !self.delete_rec(x)
     }
 
-    fn delete_rec(&mut self, x: T) -> Option<Box<BST<T>>> {
+    /// Deletes x from the subtree.
+    /// Returns true if the node itself is the one to delete and has no children, in which case
+    /// the parent has to drop it.
+    fn delete_rec(&mut self, x: T) -> bool {
         if self.key.eq(&x) {
             // Case 1: no children
             if self.left.is_none() && self.right.is_none() {
-                return None;
+                return true;
             }
             // Case 2: only left child
             if self.right.is_none() {
                 let mut left_subtree = self.left.take().unwrap();
                 // take maximum of the left subtree
-                let max = left_subtree.max();
+                let max = left_subtree.max().key;
                 // set current node value as the maximum
-                self.key = max.key;
+                self.key = max;
                 // delete the maximum recursively
-                self.left = left_subtree.delete_rec(max.key);
+                if !left_subtree.delete_rec(max) {
+                    self.left = Some(left_subtree);
+                }
             } else {
                 // Case 3, 4: both children or only right child
                 let mut right_subtree = self.right.take().unwrap();
                 // take minimum of the right subtree
-                let min = right_subtree.min();
+                let min = right_subtree.min().key;
                 // set current node value as the minimum
-                self.key = min.key;
+                self.key = min;
                 // delete the minimum recursively
-                self.right = right_subtree.delete_rec(min.key);
+                if !right_subtree.delete_rec(min) {
+                    self.right = Some(right_subtree);
+                }
             }
         } else if self.key.lt(&x) {
             if let Some(right) = self.right.as_mut() {
-                let new_right = right.delete_rec(x);
-                self.right = new_right;
+                if right.delete_rec(x) {
+                    self.right = None;
+                }
             }
         } else {
             if let Some(left) = self.left.as_mut() {
-                let new_left = left.delete_rec(x);
-                self.left = new_left;
+                if left.delete_rec(x) {
+                    self.left = None;
+                }
             }
         }
 
-        Some(Box::new(self.clone()))
+        self.update_augmentation();
+        false
     }
 
     pub fn update(&mut self, key: T, val: T) -> bool {
@@ -195,6 +260,26 @@ where
         }
     }
 
+    /// Returns the keys of the tree in increasing order.
+    ///
+    /// returns: Vec<T>
+    ///
+    pub fn in_order(&self) -> Vec<T> {
+        let mut keys = Vec::with_capacity(self.size());
+        self.in_order_rec(&mut keys);
+        keys
+    }
+
+    fn in_order_rec(&self, keys: &mut Vec<T>) {
+        if let Some(ref left) = self.left {
+            left.in_order_rec(keys);
+        }
+        keys.push(self.key);
+        if let Some(ref right) = self.right {
+            right.in_order_rec(keys);
+        }
+    }
+
     /// Prints the depth of each node in the BST<T>
     ///
     /// returns: ()
@@ -322,9 +407,10 @@ where
     }
 }
 
-impl<T> BST<T>
+impl<T, A> BST<T, A>
 where
     T: Default + Ord + Debug + Copy + Clone + Add<Output = T> + MinMax + Copy,
+    A: Augmentation<T>,
 {
     pub fn equally_distanced_nodes(&self) -> usize {
         self.equally_distanced_nodes_rec(<T>::default()).0
@@ -540,3 +626,41 @@ pub fn test_delete_couples() {
     assert!(!bst.search((4, 1)));
     assert_eq!(bst.size(), 1);
 }
+
+#[test]
+pub fn test_in_order() {
+    let bst = BST::from_vec(vec![5, 2, 8, 1, 9, 2, 7]);
+    assert_eq!(bst.in_order(), vec![1, 2, 2, 5, 7, 8, 9]);
+}
+
+/// The size of the subtree, as an example of augmentation.
+#[cfg(test)]
+#[derive(Clone, Debug, PartialEq)]
+struct Size(usize);
+
+#[cfg(test)]
+impl<T> Augmentation<T> for Size {
+    fn compute(_: &T, left: Option<&Size>, right: Option<&Size>) -> Size {
+        Size(1 + left.map_or(0, |l| l.0) + right.map_or(0, |r| r.0))
+    }
+}
+
+#[test]
+pub fn test_augmentation() {
+    fn check(bst: &BST<i32, Size>) {
+        assert_eq!(bst.augmentation().0, bst.size());
+        bst.left().into_iter().chain(bst.right()).for_each(check);
+    }
+
+    let mut bst: BST<i32, Size> = BST::with_augmented_root(5);
+    for key in [2, 8, 1, 9, 2, 7, 3, 6] {
+        bst.add(key);
+        check(&bst);
+    }
+    for key in [5, 2, 9, 1, 2, 6] {
+        assert!(bst.delete(key));
+        check(&bst);
+    }
+    assert_eq!(bst.in_order(), vec![3, 7, 8]);
+    assert_eq!(bst.augmentation(), &Size(3));
+}
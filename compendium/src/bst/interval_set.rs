@@ -0,0 +1,251 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::collections::BTreeMap;
+use std::ops::{Add, Sub};
+
+/// Interval Set: a set of points stored as disjoint half-open intervals [a, b), kept in std's
+/// BTreeMap from the start of each interval to its end, whose range queries find the intervals
+/// around a point in O(log n). Inserting an interval merges it with the ones it overlaps or
+/// touches, removing a range splits the intervals it cuts.
+pub struct IntervalSet<T>
+where
+    T: Ord + Copy,
+{
+    intervals: BTreeMap<T, T>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Ord + Copy,
+{
+    pub fn new() -> Self {
+        Self {
+            intervals: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of disjoint intervals in the set.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns the disjoint intervals of the set in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.intervals.iter().map(|(&a, &b)| (a, b))
+    }
+
+    /// Adds the points of [a, b) to the set, merging the intervals overlapping or touching it.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the interval
+    /// * `b`: the end of the interval (excluded)
+    ///
+    /// returns: ()
+    ///
+    /// \theta((k + 1) log n) where k is the number of merged intervals
+    pub fn insert(&mut self, a: T, b: T) {
+        assert!(a <= b, "Interval Set: invalid interval");
+        if a == b {
+            return;
+        }
+
+        let (mut start, mut end) = (a, b);
+        if let Some((&s, &e)) = self.intervals.range(..a).next_back() {
+            if e >= a {
+                start = s;
+                end = end.max(e);
+            }
+        }
+
+        let merged: Vec<T> = self.intervals.range(start..=b).map(|(&s, _)| s).collect();
+        for s in merged {
+            end = end.max(self.intervals.remove(&s).unwrap());
+        }
+        self.intervals.insert(start, end);
+    }
+
+    /// Removes the points of [a, b) from the set, shrinking or splitting the intervals it cuts.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the range
+    /// * `b`: the end of the range (excluded)
+    ///
+    /// returns: ()
+    ///
+    /// \theta((k + 1) log n) where k is the number of intervals cut
+    pub fn remove(&mut self, a: T, b: T) {
+        assert!(a <= b, "Interval Set: invalid interval");
+        if a == b {
+            return;
+        }
+
+        if let Some((&s, &e)) = self.intervals.range(..a).next_back() {
+            if e > a {
+                self.intervals.insert(s, a);
+                if e > b {
+                    self.intervals.insert(b, e);
+                }
+            }
+        }
+
+        let cut: Vec<T> = self.intervals.range(a..b).map(|(&s, _)| s).collect();
+        for s in cut {
+            let e = self.intervals.remove(&s).unwrap();
+            if e > b {
+                self.intervals.insert(b, e);
+            }
+        }
+    }
+
+    /// Stabbing query: returns the interval containing x, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: the query point
+    ///
+    /// returns: Option<(T, T)>
+    ///
+    /// \theta(log n)
+    pub fn stab(&self, x: T) -> Option<(T, T)> {
+        self.intervals
+            .range(..=x)
+            .next_back()
+            .filter(|(_, &e)| x < e)
+            .map(|(&s, &e)| (s, e))
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.stab(x).is_some()
+    }
+
+    /// Returns the intervals of the set overlapping [a, b), in increasing order.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the query interval
+    /// * `b`: the end of the query interval (excluded)
+    ///
+    /// returns: Vec<(T, T)>
+    ///
+    /// \theta(k + log n) where k is the number of intervals returned
+    pub fn overlapping(&self, a: T, b: T) -> Vec<(T, T)> {
+        if a >= b {
+            return Vec::new();
+        }
+
+        let first = self
+            .intervals
+            .range(..a)
+            .next_back()
+            .filter(|(_, &e)| e > a);
+        first
+            .into_iter()
+            .chain(self.intervals.range(a..b))
+            .map(|(&s, &e)| (s, e))
+            .collect()
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Ord + Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Returns the number of points in the set, i.e. the total length of its intervals.
+    ///
+    /// returns: T
+    ///
+    /// \theta(n)
+    pub fn total_length(&self) -> T {
+        self.iter().fold(T::default(), |acc, (a, b)| acc + (b - a))
+    }
+}
+
+impl<T> Default for IntervalSet<T>
+where
+    T: Ord + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(T, T)> for IntervalSet<T>
+where
+    T: Ord + Copy,
+{
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for (a, b) in iter {
+            set.insert(a, b);
+        }
+        set
+    }
+}
+
+#[test]
+pub fn test_interval_set() {
+    let mut set: IntervalSet<i32> = [(1, 3), (5, 8), (3, 4), (10, 12)].into_iter().collect();
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![(1, 4), (5, 8), (10, 12)]
+    );
+    assert_eq!(set.total_length(), 8);
+
+    set.insert(7, 10);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![(1, 4), (5, 12)]);
+
+    set.remove(2, 6);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![(1, 2), (6, 12)]);
+    set.remove(8, 9);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![(1, 2), (6, 8), (9, 12)]
+    );
+
+    assert_eq!(set.stab(7), Some((6, 8)));
+    assert_eq!(set.stab(8), None);
+    assert!(set.contains(1));
+    assert!(!set.contains(2));
+    assert_eq!(set.overlapping(7, 10), vec![(6, 8), (9, 12)]);
+    assert_eq!(set.overlapping(2, 6), vec![]);
+
+    set.remove(-100, 100);
+    assert!(set.is_empty());
+}
+
+#[test]
+pub fn test_interval_set_brute_force() {
+    let mut set = IntervalSet::new();
+    let mut points = [false; 60];
+
+    let mut rng = Lcg::new(3);
+    for _ in 0..500 {
+        let state = rng.next_u64() as usize;
+        let a = state % 50;
+        let b = a + (state / 50) % 10;
+        match state % 3 {
+            0 => {
+                set.remove(a, b);
+                points[a..b].iter_mut().for_each(|p| *p = false);
+            }
+            _ => {
+                set.insert(a, b);
+                points[a..b].iter_mut().for_each(|p| *p = true);
+            }
+        }
+
+        // the intervals are disjoint, non adjacent and cover exactly the points
+        let intervals: Vec<_> = set.iter().collect();
+        assert!(intervals.windows(2).all(|w| w[0].1 < w[1].0));
+        for (x, &p) in points.iter().enumerate() {
+            assert_eq!(set.contains(x), p);
+        }
+        assert_eq!(set.total_length(), points.iter().filter(|&&p| p).count());
+    }
+}
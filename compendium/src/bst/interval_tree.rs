@@ -0,0 +1,326 @@
+use crate::bst::binary_search_trees::{Augmentation, BST};
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use crate::utilities::MinMax;
+use std::fmt::Debug;
+
+/// The maximum end of the intervals in a subtree.
+#[derive(Clone)]
+struct MaxEnd<T>(T);
+
+impl<T> Augmentation<(T, T)> for MaxEnd<T>
+where
+    T: Ord + Copy,
+{
+    fn compute(interval: &(T, T), left: Option<&Self>, right: Option<&Self>) -> Self {
+        MaxEnd(
+            [left, right]
+                .into_iter()
+                .flatten()
+                .fold(interval.1, |max_end, child| max_end.max(child.0)),
+        )
+    }
+}
+
+type Node<T> = BST<(T, T), MaxEnd<T>>;
+
+/// Interval Tree: the compendium BST of half-open intervals [a, b), ordered by (a, b) as it
+/// orders its tuple keys, where every node is augmented with the maximum end in its subtree.
+/// The augmentation allows to skip the subtrees that can not overlap a query.
+/// Intervals may overlap each other and duplicates are kept.
+pub struct IntervalTree<T>
+where
+    T: Default + Ord + Debug + MinMax + Copy,
+{
+    root: Option<Node<T>>,
+    len: usize,
+}
+
+impl<T> IntervalTree<T>
+where
+    T: Default + Ord + Debug + MinMax + Copy,
+{
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    /// Builds a balanced interval tree from the given intervals.
+    ///
+    /// # Arguments
+    ///
+    /// * `intervals`: the intervals [a, b) with a <= b
+    ///
+    /// returns: IntervalTree<T>
+    ///
+    /// \theta(n log n)
+    pub fn from_vec(mut intervals: Vec<(T, T)>) -> Self {
+        intervals.sort();
+        let mut tree = Self::new();
+        tree.insert_balanced(&intervals);
+        tree
+    }
+
+    /// Builds a balanced interval tree from the intervals stored as keys of a BST.
+    ///
+    /// # Arguments
+    ///
+    /// * `bst`: the BST of intervals [a, b) with a <= b
+    ///
+    /// returns: IntervalTree<T>
+    ///
+    /// \theta(n log n)
+    pub fn from_bst(bst: &BST<(T, T)>) -> Self {
+        let mut tree = Self::new();
+        tree.insert_balanced(&bst.in_order());
+        tree
+    }
+
+    /// Inserts the sorted intervals medians first, so that the BST stays balanced.
+    fn insert_balanced(&mut self, sorted: &[(T, T)]) {
+        if sorted.is_empty() {
+            return;
+        }
+
+        let mid = sorted.len() / 2;
+        self.insert(sorted[mid].0, sorted[mid].1);
+        self.insert_balanced(&sorted[..mid]);
+        self.insert_balanced(&sorted[mid + 1..]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the intervals of the tree in increasing order.
+    ///
+    /// returns: Vec<(T, T)>
+    ///
+    /// \theta(n)
+    pub fn intervals(&self) -> Vec<(T, T)> {
+        self.root
+            .as_ref()
+            .map_or(Vec::new(), |root| root.in_order())
+    }
+
+    /// Adds the interval [a, b) to the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the interval
+    /// * `b`: the end of the interval (excluded)
+    ///
+    /// returns: ()
+    ///
+    /// \theta(h)
+    pub fn insert(&mut self, a: T, b: T) {
+        assert!(a <= b, "Interval Tree: invalid interval");
+
+        match self.root.as_mut() {
+            None => self.root = Some(BST::with_augmented_root((a, b))),
+            Some(root) => root.add((a, b)),
+        }
+        self.len += 1;
+    }
+
+    /// Removes one copy of the interval [a, b) from the tree.
+    /// Returns false if the interval is not in the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the interval
+    /// * `b`: the end of the interval (excluded)
+    ///
+    /// returns: bool
+    ///
+    /// \theta(h)
+    pub fn remove(&mut self, a: T, b: T) -> bool {
+        let Some(root) = self.root.as_mut() else {
+            return false;
+        };
+
+        // the BST can not delete its own root when it is the last node
+        let removed = if root.key() == (a, b) && root.left().is_none() && root.right().is_none() {
+            self.root = None;
+            true
+        } else {
+            root.delete((a, b))
+        };
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Stabbing query: returns the intervals containing x, in increasing order.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: the query point
+    ///
+    /// returns: Vec<(T, T)>
+    ///
+    /// \theta(h + k log n) where k is the number of intervals returned
+    pub fn stabbing(&self, x: T) -> Vec<(T, T)> {
+        let mut result = Vec::new();
+        Self::overlapping_rec(self.root.as_ref(), x, x, true, &mut result);
+        result
+    }
+
+    /// Returns the intervals overlapping [a, b), in increasing order.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the query interval
+    /// * `b`: the end of the query interval (excluded)
+    ///
+    /// returns: Vec<(T, T)>
+    ///
+    /// \theta(h + k log n) where k is the number of intervals returned
+    pub fn overlapping(&self, a: T, b: T) -> Vec<(T, T)> {
+        let mut result = Vec::new();
+        Self::overlapping_rec(self.root.as_ref(), a, b, false, &mut result);
+        result
+    }
+
+    /// Collects the intervals [c, d) with c < b and a < d, or with c <= a < d if `point` is set.
+    fn overlapping_rec(node: Option<&Node<T>>, a: T, b: T, point: bool, result: &mut Vec<(T, T)>) {
+        let Some(node) = node else {
+            return;
+        };
+
+        // every interval of the subtree ends before the query starts
+        if node.augmentation().0 <= a {
+            return;
+        }
+
+        Self::overlapping_rec(node.left(), a, b, point, result);
+
+        let (start, end) = node.key();
+        let before_end = if point { start <= b } else { start < b };
+        if before_end {
+            if a < end {
+                result.push((start, end));
+            }
+            // the right subtree starts after the current interval
+            Self::overlapping_rec(node.right(), a, b, point, result);
+        }
+    }
+
+    /// Returns any interval overlapping [a, b), if one exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the start of the query interval
+    /// * `b`: the end of the query interval (excluded)
+    ///
+    /// returns: Option<(T, T)>
+    ///
+    /// \theta(h)
+    pub fn find_overlap(&self, a: T, b: T) -> Option<(T, T)> {
+        let mut current = self.root.as_ref();
+        while let Some(node) = current {
+            let (start, end) = node.key();
+            if start < b && a < end {
+                return Some((start, end));
+            }
+            // if some interval on the left ends after a but none overlaps, it starts after b,
+            // and so does every interval on the right
+            current = match node.left() {
+                Some(left) if left.augmentation().0 > a => Some(left),
+                _ => node.right(),
+            };
+        }
+        None
+    }
+}
+
+impl<T> Default for IntervalTree<T>
+where
+    T: Default + Ord + Debug + MinMax + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+fn pseudo_random_intervals(n: usize, seed: u64) -> Vec<(i32, i32)> {
+    let mut rng = Lcg::new(seed);
+    (0..n)
+        .map(|_| {
+            let state = rng.next_u64();
+            let a = (state % 50) as i32 - 25;
+            (a, a + (state / 50 % 10) as i32)
+        })
+        .collect()
+}
+
+#[test]
+pub fn test_interval_tree() {
+    let mut tree = IntervalTree::from_vec(vec![(15, 20), (10, 30), (17, 19), (5, 20), (12, 15)]);
+    tree.insert(30, 40);
+
+    assert_eq!(tree.len(), 6);
+    assert_eq!(tree.stabbing(16), vec![(5, 20), (10, 30), (15, 20)]);
+    assert_eq!(tree.stabbing(30), vec![(30, 40)]);
+    assert_eq!(tree.stabbing(4), vec![]);
+    assert_eq!(tree.overlapping(19, 21), vec![(5, 20), (10, 30), (15, 20)]);
+    assert!(tree.find_overlap(18, 19).is_some());
+    assert_eq!(tree.find_overlap(40, 50), None);
+
+    assert!(tree.remove(10, 30));
+    assert!(!tree.remove(10, 30));
+    assert_eq!(tree.stabbing(25), vec![]);
+    assert_eq!(tree.len(), 5);
+}
+
+#[test]
+pub fn test_interval_tree_from_bst() {
+    let bst = BST::from_vec(vec![(3, 8), (1, 2), (6, 7), (0, 10)]);
+    let tree = IntervalTree::from_bst(&bst);
+    assert_eq!(tree.intervals(), bst.in_order());
+    assert_eq!(tree.stabbing(6), vec![(0, 10), (3, 8), (6, 7)]);
+}
+
+#[test]
+pub fn test_interval_tree_brute_force() {
+    let intervals = pseudo_random_intervals(60, 7);
+    let mut tree = IntervalTree::new();
+    let mut expected: Vec<(i32, i32)> = Vec::new();
+
+    for (i, &(a, b)) in intervals.iter().enumerate() {
+        tree.insert(a, b);
+        expected.push((a, b));
+        // remove every third interval inserted so far
+        if i % 3 == 2 {
+            let removed = expected.remove(i / 3);
+            assert!(tree.remove(removed.0, removed.1));
+        }
+        expected.sort();
+        assert_eq!(tree.intervals(), expected);
+
+        for x in -30..40 {
+            let stabbed: Vec<_> = expected
+                .iter()
+                .copied()
+                .filter(|&(a, b)| a <= x && x < b)
+                .collect();
+            assert_eq!(tree.stabbing(x), stabbed);
+
+            let overlapping: Vec<_> = expected
+                .iter()
+                .copied()
+                .filter(|&(a, b)| a < x + 3 && x < b)
+                .collect();
+            assert_eq!(tree.overlapping(x, x + 3), overlapping);
+            assert_eq!(
+                tree.find_overlap(x, x + 3).is_some(),
+                !overlapping.is_empty()
+            );
+        }
+    }
+}
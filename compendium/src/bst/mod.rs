@@ -1,2 +1,4 @@
 mod binary_search_trees;
 mod frogs_and_mosquitoes;
+mod interval_set;
+mod interval_tree;
@@ -0,0 +1,361 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::ops::{Add, Mul, Sub};
+
+/// Iterator over the submasks of a mask, from the mask itself down to 0.
+pub struct Submasks {
+    mask: usize,
+    next: Option<usize>,
+}
+
+impl Iterator for Submasks {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        // the next smaller submask: remove the lowest set bit and refill the ones below it
+        self.next = current.checked_sub(1).map(|s| s & self.mask);
+        Some(current)
+    }
+}
+
+/// Returns an iterator over the submasks of the mask in decreasing order, 0 included.
+/// Iterating over the submasks of every mask of n bits costs \theta(3^n) overall.
+///
+/// # Arguments
+///
+/// * `mask`: the mask
+///
+/// returns: Submasks
+/// \theta(2^popcount(mask))
+pub fn submasks(mask: usize) -> Submasks {
+    Submasks {
+        mask,
+        next: Some(mask),
+    }
+}
+
+/// Iterator over the masks of n bits with exactly k ones, in increasing order.
+pub struct FixedPopcount {
+    n: usize,
+    next: Option<usize>,
+}
+
+impl Iterator for FixedPopcount {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current = self.next?;
+        // Gosper's hack: move the lowest block of ones one position up, the rest of the block
+        // going back to the bottom
+        self.next = match current {
+            0 => None,
+            _ => {
+                let lowest = current & current.wrapping_neg();
+                let carried = current + lowest;
+                let next = carried | (((current ^ carried) >> 2) / lowest);
+                (next < 1 << self.n).then_some(next)
+            }
+        };
+        Some(current)
+    }
+}
+
+/// Returns an iterator over the masks of n bits with exactly k ones, in increasing order.
+///
+/// # Arguments
+///
+/// * `n`: the number of bits, less than the bits of a usize
+/// * `k`: the number of ones
+///
+/// returns: FixedPopcount
+/// \theta(1) per mask
+pub fn masks_with_popcount(n: usize, k: usize) -> FixedPopcount {
+    assert!(n < usize::BITS as usize, "Bitmask: too many bits");
+    FixedPopcount {
+        n,
+        next: (k <= n).then(|| (1 << k) - 1),
+    }
+}
+
+/// Held-Karp: the shortest tour visiting every vertex exactly once and coming back to vertex 0.
+/// best[S][v] is the shortest path from 0 visiting exactly the vertices in S and ending in v,
+/// extended one vertex at a time.
+///
+/// # Arguments
+///
+/// * `dist`: the length of the edge from u to v, None if missing
+///
+/// returns: Option<(i64, Vec<usize>)>, the length of the tour and its vertices starting from 0,
+/// None if there is no tour
+/// \theta(2^n * n^2)
+pub fn held_karp(dist: &[Vec<Option<i64>>]) -> Option<(i64, Vec<usize>)> {
+    let n = dist.len();
+    assert!(
+        dist.iter().all(|row| row.len() == n),
+        "Held-Karp: the distance matrix must be square"
+    );
+    match n {
+        0 => return None,
+        1 => return Some((0, vec![0])),
+        _ => {}
+    }
+
+    let full = (1 << n) - 1;
+    let mut best: Vec<Vec<Option<i64>>> = vec![vec![None; n]; 1 << n];
+    let mut parent = vec![vec![0; n]; 1 << n];
+    best[1][0] = Some(0);
+
+    // the masks always contain 0, and increase along the extensions
+    for mask in (1..=full).step_by(2) {
+        for v in 0..n {
+            let Some(length) = best[mask][v] else {
+                continue;
+            };
+            for (u, &edge) in dist[v].iter().enumerate() {
+                let (Some(edge), 0) = (edge, mask >> u & 1) else {
+                    continue;
+                };
+                let extended = mask | 1 << u;
+                if best[extended][u].is_none_or(|b| length + edge < b) {
+                    best[extended][u] = Some(length + edge);
+                    parent[extended][u] = v;
+                }
+            }
+        }
+    }
+
+    let (length, last) = (1..n)
+        .filter_map(|v| Some((best[full][v]? + dist[v][0]?, v)))
+        .min()?;
+
+    let mut tour = vec![last];
+    let mut mask = full;
+    let mut v = last;
+    while v != 0 {
+        let previous = parent[mask][v];
+        mask ^= 1 << v;
+        v = previous;
+        tour.push(v);
+    }
+    tour.reverse();
+    Some((length, tour))
+}
+
+/// Zeta transform over subsets (sum over subsets): f[S] becomes the sum of f[T] over T ⊆ S,
+/// adding one bit at a time.
+///
+/// # Arguments
+///
+/// * `f`: the values over the masks of n bits, 2^n of them
+///
+/// returns: ()
+/// \theta(2^n * n)
+pub fn subset_zeta<T>(f: &mut [T])
+where
+    T: Copy + Add<Output = T>,
+{
+    assert!(f.len().is_power_of_two(), "SOS: the length must be 2^n");
+    let mut bit = 1;
+    while bit < f.len() {
+        for mask in 0..f.len() {
+            if mask & bit != 0 {
+                f[mask] = f[mask] + f[mask ^ bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Möbius transform over subsets, the inverse of `subset_zeta`.
+///
+/// # Arguments
+///
+/// * `f`: the values over the masks of n bits, 2^n of them
+///
+/// returns: ()
+/// \theta(2^n * n)
+pub fn subset_mobius<T>(f: &mut [T])
+where
+    T: Copy + Sub<Output = T>,
+{
+    assert!(f.len().is_power_of_two(), "SOS: the length must be 2^n");
+    let mut bit = 1;
+    while bit < f.len() {
+        for mask in 0..f.len() {
+            if mask & bit != 0 {
+                f[mask] = f[mask] - f[mask ^ bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// Subset convolution: h[S] is the sum of f[T] * g[S \ T] over T ⊆ S. The values are split by
+/// popcount, so that in the product of the zeta transforms of the ranks i and |S| - i only
+/// disjoint pairs with union S survive the Möbius transform.
+///
+/// # Arguments
+///
+/// * `f`: the values over the masks of n bits, 2^n of them
+/// * `g`: the values over the masks of n bits, 2^n of them
+///
+/// returns: Vec<T>
+/// \theta(2^n * n^2)
+pub fn subset_convolution<T>(f: &[T], g: &[T]) -> Vec<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert_eq!(f.len(), g.len());
+    assert!(f.len().is_power_of_two(), "SOS: the length must be 2^n");
+    let size = f.len();
+    let n = size.trailing_zeros() as usize;
+
+    let ranked = |a: &[T]| -> Vec<Vec<T>> {
+        let mut ranks = vec![vec![T::default(); size]; n + 1];
+        for (mask, &value) in a.iter().enumerate() {
+            ranks[mask.count_ones() as usize][mask] = value;
+        }
+        for rank in ranks.iter_mut() {
+            subset_zeta(rank);
+        }
+        ranks
+    };
+    let (f_ranks, g_ranks) = (ranked(f), ranked(g));
+
+    let mut h = vec![T::default(); size];
+    for k in 0..=n {
+        let mut product = vec![T::default(); size];
+        for i in 0..=k {
+            for (mask, cell) in product.iter_mut().enumerate() {
+                *cell = *cell + f_ranks[i][mask] * g_ranks[k - i][mask];
+            }
+        }
+        subset_mobius(&mut product);
+        for (mask, &value) in product.iter().enumerate() {
+            if mask.count_ones() as usize == k {
+                h[mask] = value;
+            }
+        }
+    }
+    h
+}
+
+#[test]
+pub fn test_submasks() {
+    assert_eq!(
+        submasks(0b1011).collect::<Vec<_>>(),
+        vec![0b1011, 0b1010, 0b1001, 0b1000, 0b0011, 0b0010, 0b0001, 0]
+    );
+    assert_eq!(submasks(0).collect::<Vec<_>>(), vec![0]);
+
+    // 3^n pairs (mask, submask) over all the masks of n bits
+    let n = 7;
+    let pairs: usize = (0..1 << n).map(|mask| submasks(mask).count()).sum();
+    assert_eq!(pairs, 3_usize.pow(n));
+    assert!((0..1 << n).all(|mask| submasks(mask).all(|s| s & !mask == 0)));
+}
+
+#[test]
+pub fn test_masks_with_popcount() {
+    assert_eq!(
+        masks_with_popcount(4, 2).collect::<Vec<_>>(),
+        vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]
+    );
+    assert_eq!(masks_with_popcount(5, 0).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(masks_with_popcount(3, 3).collect::<Vec<_>>(), vec![0b111]);
+    assert_eq!(masks_with_popcount(3, 4).count(), 0);
+
+    for n in 0..10 {
+        for k in 0..=n {
+            let expected: Vec<usize> = (0..1 << n)
+                .filter(|m: &usize| m.count_ones() as usize == k)
+                .collect();
+            assert_eq!(masks_with_popcount(n, k).collect::<Vec<_>>(), expected);
+        }
+    }
+}
+
+#[test]
+pub fn test_held_karp() {
+    let x = None;
+    let dist = vec![
+        vec![x, Some(10), Some(15), Some(20)],
+        vec![Some(10), x, Some(35), Some(25)],
+        vec![Some(15), Some(35), x, Some(30)],
+        vec![Some(20), Some(25), Some(30), x],
+    ];
+    let (length, tour) = held_karp(&dist).unwrap();
+    assert_eq!(length, 80);
+    assert!(tour == vec![0, 1, 3, 2] || tour == vec![0, 2, 3, 1]);
+    assert_eq!(held_karp(&[vec![None]]), Some((0, vec![0])));
+    assert_eq!(held_karp(&[vec![x, Some(1)], vec![x, x]]), None);
+
+    // brute force over the permutations, on random directed graphs with missing edges
+    fn permutations(rest: &mut Vec<usize>, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        if rest.is_empty() {
+            out.push(prefix.clone());
+        }
+        for i in 0..rest.len() {
+            let v = rest.remove(i);
+            prefix.push(v);
+            permutations(rest, prefix, out);
+            prefix.pop();
+            rest.insert(i, v);
+        }
+    }
+    let length_of = |dist: &[Vec<Option<i64>>], tour: &[usize]| -> Option<i64> {
+        (0..tour.len())
+            .map(|i| dist[tour[i]][tour[(i + 1) % tour.len()]])
+            .sum()
+    };
+
+    let mut rng = Lcg::new(31);
+    for n in 2..8 {
+        let mut dist = vec![vec![None; n]; n];
+        for (u, row) in dist.iter_mut().enumerate() {
+            for (v, cell) in row.iter_mut().enumerate() {
+                let state = rng.next_u64();
+                if u != v && !state.is_multiple_of(5) {
+                    *cell = Some(state as i64 % 50);
+                }
+            }
+        }
+
+        let mut tours = Vec::new();
+        permutations(&mut (1..n).collect(), &mut vec![0], &mut tours);
+        let best = tours.iter().filter_map(|t| length_of(&dist, t)).min();
+        let result = held_karp(&dist);
+        assert_eq!(result.as_ref().map(|(l, _)| *l), best);
+        if let Some((length, tour)) = result {
+            assert_eq!(length_of(&dist, &tour), Some(length));
+            let mut sorted = tour.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+pub fn test_subset_transforms() {
+    let n = 6;
+    let mut rng = Lcg::new(13);
+    let mut random = || rng.below(21) as i64 - 10;
+    let f: Vec<i64> = (0..1 << n).map(|_| random()).collect();
+    let g: Vec<i64> = (0..1 << n).map(|_| random()).collect();
+
+    let mut zeta = f.clone();
+    subset_zeta(&mut zeta);
+    for (mask, &value) in zeta.iter().enumerate() {
+        assert_eq!(value, submasks(mask).map(|s| f[s]).sum::<i64>());
+    }
+    subset_mobius(&mut zeta);
+    assert_eq!(zeta, f);
+
+    let h = subset_convolution(&f, &g);
+    for mask in 0..1 << n {
+        let expected: i64 = submasks(mask).map(|s| f[s] * g[mask ^ s]).sum();
+        assert_eq!(h[mask], expected);
+    }
+    assert_eq!(subset_convolution(&[3], &[4]), vec![12]);
+}
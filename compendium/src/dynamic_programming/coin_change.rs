@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+
+/// Counts the ways to obtain k as a sum of coins of the given values, each available any
+/// number of times, where the order of the coins does not matter.
+///
+/// # Arguments
+///
+/// * `c`: the values of the coins
+/// * `k`: the target sum
+///
+/// returns: usize
+/// \theta(n * k) time, \theta(k) memory
+pub fn coin_change(c: Vec<usize>, k: usize) -> usize {
+    let mut ways = vec![0; k + 1];
+    ways[0] = 1;
+    for &coin in c.iter().filter(|&&coin| coin > 0) {
+        for j in coin..=k {
+            ways[j] += ways[j - coin];
+        }
+    }
+    ways[k]
+}
+
+/// Counts the multisets of coins with sum k modulo p, i.e. the ways to change k when the order
+/// of the coins does not matter. The coins are the outer loop, so every multiset is built in
+/// a single order.
+///
+/// # Arguments
+///
+/// * `c`: the values of the coins, all positive
+/// * `k`: the target sum
+/// * `p`: the modulus, usually a large prime
+///
+/// returns: u64
+/// \theta(n * k) time, \theta(k) memory
+pub fn count_combinations(c: &[usize], k: usize, p: u64) -> u64 {
+    assert_valid(c, p);
+
+    let mut ways = vec![0; k + 1];
+    ways[0] = 1 % p;
+    for &coin in c {
+        for j in coin..=k {
+            ways[j] = (ways[j] + ways[j - coin]) % p;
+        }
+    }
+    ways[k]
+}
+
+/// Counts the sequences of coins with sum k modulo p, i.e. the ways to change k when the order
+/// of the coins matters. The sums are the outer loop, so every last coin is tried for each.
+///
+/// # Arguments
+///
+/// * `c`: the values of the coins, all positive
+/// * `k`: the target sum
+/// * `p`: the modulus, usually a large prime
+///
+/// returns: u64
+/// \theta(n * k) time, \theta(k) memory
+pub fn count_compositions(c: &[usize], k: usize, p: u64) -> u64 {
+    assert_valid(c, p);
+
+    let mut ways = vec![0; k + 1];
+    ways[0] = 1 % p;
+    for j in 1..=k {
+        for &coin in c.iter().filter(|&&coin| coin <= j) {
+            ways[j] = (ways[j] + ways[j - coin]) % p;
+        }
+    }
+    ways[k]
+}
+
+/// Counts the multisets of coins with sum k modulo p, when only supply[i] coins of value c[i]
+/// are available. Using t coins of value c adds ways[j - t * c] for t in [0, supply], a sliding
+/// window over the sums with the same remainder modulo c, kept as a running sum.
+///
+/// # Arguments
+///
+/// * `c`: the values of the coins, all positive
+/// * `supply`: the number of available coins of each value
+/// * `k`: the target sum
+/// * `p`: the modulus, usually a large prime
+///
+/// returns: u64
+/// \theta(n * k) time, \theta(k) memory
+pub fn count_combinations_bounded(c: &[usize], supply: &[usize], k: usize, p: u64) -> u64 {
+    assert_valid(c, p);
+    assert_eq!(c.len(), supply.len());
+
+    let mut ways = vec![0; k + 1];
+    ways[0] = 1 % p;
+    for (&coin, &available) in c.iter().zip(supply) {
+        let previous = ways.clone();
+        // the sum of the window ending in j - coin, plus the new term, minus the one leaving it
+        let window = available.checked_add(1).and_then(|t| coin.checked_mul(t));
+        for j in coin..=k {
+            let mut total = previous[j] + ways[j - coin];
+            if let Some(leaving) = window.and_then(|w| j.checked_sub(w)) {
+                total += p - previous[leaving];
+            }
+            ways[j] = total % p;
+        }
+    }
+    ways[k]
+}
+
+/// Returns the minimum number of coins with sum k, and how many coins of each value are used,
+/// or None if k can not be obtained. The last coin used for every sum is enough to rebuild the
+/// solution.
+///
+/// # Arguments
+///
+/// * `c`: the values of the coins, all positive
+/// * `k`: the target sum
+///
+/// returns: Option<(usize, Vec<usize>)>
+/// \theta(n * k) time, \theta(k) memory
+pub fn min_coins(c: &[usize], k: usize) -> Option<(usize, Vec<usize>)> {
+    assert_valid(c, 1);
+
+    let mut best: Vec<Option<usize>> = vec![None; k + 1];
+    let mut last = vec![0; k + 1];
+    best[0] = Some(0);
+    for j in 1..=k {
+        for (i, &coin) in c.iter().enumerate().filter(|&(_, &coin)| coin <= j) {
+            if let Some(coins) = best[j - coin] {
+                if best[j].is_none_or(|b| coins + 1 < b) {
+                    best[j] = Some(coins + 1);
+                    last[j] = i;
+                }
+            }
+        }
+    }
+
+    let total = best[k]?;
+    let mut used = vec![0; c.len()];
+    let mut j = k;
+    while j > 0 {
+        used[last[j]] += 1;
+        j -= c[last[j]];
+    }
+    Some((total, used))
+}
+
+/// Returns the minimum number of coins with sum k when only supply[i] coins of value c[i] are
+/// available, or None if k can not be obtained. For the sums j = r + q * c with the same
+/// remainder r, using t coins of value c gives best[r + (q - t) * c] + t, so the minimum over
+/// t in [0, supply] is a sliding window minimum of best[r + q' * c] - q', kept in a monotone
+/// deque.
+///
+/// # Arguments
+///
+/// * `c`: the values of the coins, all positive
+/// * `supply`: the number of available coins of each value
+/// * `k`: the target sum
+///
+/// returns: Option<usize>
+/// \theta(n * k) time, \theta(k) memory
+pub fn min_coins_bounded(c: &[usize], supply: &[usize], k: usize) -> Option<usize> {
+    assert_valid(c, 1);
+    assert_eq!(c.len(), supply.len());
+
+    let mut best: Vec<Option<usize>> = vec![None; k + 1];
+    best[0] = Some(0);
+    for (&coin, &available) in c.iter().zip(supply) {
+        let previous = best.clone();
+        for r in 0..coin.min(k + 1) {
+            // (q', best[r + q' * coin] - q') with increasing q' and increasing values
+            let mut window: VecDeque<(usize, i64)> = VecDeque::new();
+            for (q, j) in (r..=k).step_by(coin).enumerate() {
+                if let Some(coins) = previous[j] {
+                    let value = coins as i64 - q as i64;
+                    while window.back().is_some_and(|&(_, v)| v >= value) {
+                        window.pop_back();
+                    }
+                    window.push_back((q, value));
+                }
+                while window
+                    .front()
+                    .is_some_and(|&(q0, _)| q0.saturating_add(available) < q)
+                {
+                    window.pop_front();
+                }
+                best[j] = window.front().map(|&(_, v)| (v + q as i64) as usize);
+            }
+        }
+    }
+    best[k]
+}
+
+fn assert_valid(c: &[usize], p: u64) {
+    assert!(
+        c.iter().all(|&coin| coin > 0),
+        "Coin Change: coins must have positive value"
+    );
+    assert!(p > 0, "Coin Change: the modulus must be positive");
+}
+
+/// Enumerates every choice of at most supply[i] coins of value c[i] with sum k, returning the
+/// number of choices, the number of orderings and the minimum number of coins.
+#[cfg(test)]
+fn brute_force(c: &[usize], supply: &[usize], k: usize) -> (u64, u64, Option<usize>) {
+    fn rec(
+        c: &[usize],
+        supply: &[usize],
+        k: usize,
+        counts: &mut Vec<u64>,
+    ) -> (u64, u64, Option<usize>) {
+        let i = counts.len();
+        if i == c.len() {
+            if k != 0 {
+                return (0, 0, None);
+            }
+            // multinomial coefficient: the orderings of the chosen coins
+            let total: u64 = counts.iter().sum();
+            let mut orderings = 1_u64;
+            let mut placed = 0;
+            for &count in counts.iter() {
+                for t in 1..=count {
+                    placed += 1;
+                    orderings = orderings * placed / t;
+                }
+            }
+            return (1, orderings, Some(total as usize));
+        }
+
+        let mut result: (u64, u64, Option<usize>) = (0, 0, None);
+        let mut t = 0;
+        while t <= supply[i] && t * c[i] <= k {
+            counts.push(t as u64);
+            let (ways, ordered, min) = rec(c, supply, k - t * c[i], counts);
+            counts.pop();
+            result.0 += ways;
+            result.1 += ordered;
+            result.2 = match (result.2, min) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            t += 1;
+        }
+        result
+    }
+
+    rec(c, supply, k, &mut Vec::new())
+}
+
+#[test]
+pub fn test_coin_change() {
+    let c = vec![1, 2, 3];
+    assert_eq!(coin_change(c, 4), 4);
+    let c = vec![2, 5, 3, 6];
+    assert_eq!(coin_change(c, 10), 5);
+}
+
+#[test]
+pub fn test_coin_change_brute_force() {
+    let c = [3, 1, 5, 4];
+    let unlimited = [usize::MAX; 4];
+    let supply = [2, 3, 1, 2];
+    let p = 1_000_000_007;
+
+    for k in 0..25 {
+        let (ways, ordered, min) = brute_force(&c, &[k; 4], k);
+        assert_eq!(count_combinations(&c, k, p), ways);
+        assert_eq!(coin_change(c.to_vec(), k) as u64, ways);
+        assert_eq!(count_compositions(&c, k, p), ordered);
+        assert_eq!(min_coins(&c, k).map(|(total, _)| total), min);
+        assert_eq!(min_coins_bounded(&c, &unlimited, k), min);
+
+        let (used, chosen) = min_coins(&c, k).unwrap();
+        assert_eq!(chosen.iter().sum::<usize>(), used);
+        assert_eq!((0..4).map(|i| chosen[i] * c[i]).sum::<usize>(), k);
+
+        let (ways, _, min) = brute_force(&c, &supply, k);
+        assert_eq!(count_combinations_bounded(&c, &supply, k, p), ways);
+        assert_eq!(count_combinations_bounded(&c, &supply, k, 7), ways % 7);
+        assert_eq!(min_coins_bounded(&c, &supply, k), min);
+    }
+}
+
+#[test]
+pub fn test_coin_change_modulo() {
+    // the number of compositions of k with parts 1 and 2 is the fibonacci number F(k + 1)
+    let p = 1_000_000_007;
+    assert_eq!(
+        count_compositions(&[1, 2], 90, p),
+        4_660_046_610_375_530_309 % p
+    );
+    assert_eq!(count_combinations(&[1, 2], 90, p), 46);
+    assert_eq!(min_coins(&[4, 6], 7), None);
+    assert_eq!(min_coins(&[1, 3, 4], 6), Some((2, vec![0, 2, 0])));
+    assert_eq!(min_coins_bounded(&[1, 3, 4], &[5, 1, 2], 6), Some(3));
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A property of numbers checked by reading their digits in some base, most significant
+/// first, without leading zeros (0 being read as the single digit 0).
+pub trait DigitMachine {
+    type State: Clone + Eq + Hash;
+
+    /// The state before reading any digit.
+    fn start(&self) -> Self::State;
+
+    /// The state after reading the digit, None if no number with these digits has the property.
+    fn step(&self, state: &Self::State, digit: u32) -> Option<Self::State>;
+
+    /// Whether the numbers ending in this state have the property.
+    fn accepts(&self, state: &Self::State) -> bool;
+}
+
+/// The digits of n in the given base, most significant first.
+fn digits(mut n: u64, base: u32) -> Vec<u32> {
+    let mut digits = vec![(n % base as u64) as u32];
+    n /= base as u64;
+    while n > 0 {
+        digits.push((n % base as u64) as u32);
+        n /= base as u64;
+    }
+    digits.reverse();
+    digits
+}
+
+struct DigitDp<'a, M: DigitMachine> {
+    machine: &'a M,
+    base: u32,
+    digits: Vec<u32>,
+    // powers[i] is base^i
+    powers: Vec<u128>,
+    // (count, sum) of the accepted completions of the free positions from i in a state
+    memo: HashMap<(usize, M::State), (u128, u128)>,
+}
+
+impl<M: DigitMachine> DigitDp<'_, M> {
+    /// Returns the number and the sum of the accepted completions of the digits from position
+    /// i, the value of the completion being the one of the remaining digits. While tight, the
+    /// digits so far are the ones of the bound and the next can not exceed the bound's; while
+    /// not started only leading zeros have been read, and the machine is still in its start.
+    fn solve(&mut self, i: usize, state: M::State, tight: bool, started: bool) -> (u128, u128) {
+        let remaining = self.digits.len() - i;
+        if remaining == 0 {
+            let accepted = match started {
+                true => self.machine.accepts(&state),
+                // the number 0
+                false => self
+                    .machine
+                    .step(&state, 0)
+                    .is_some_and(|s| self.machine.accepts(&s)),
+            };
+            return (accepted as u128, 0);
+        }
+
+        let memoized = !tight && started;
+        if memoized {
+            if let Some(&result) = self.memo.get(&(i, state.clone())) {
+                return result;
+            }
+        }
+
+        let limit = match tight {
+            true => self.digits[i],
+            false => self.base - 1,
+        };
+        let (mut count, mut sum) = (0, 0);
+        for digit in 0..=limit {
+            let next_tight = tight && digit == limit;
+            let (c, s) = match (started, digit) {
+                (false, 0) => self.solve(i + 1, state.clone(), next_tight, false),
+                _ => match self.machine.step(&state, digit) {
+                    Some(next) => self.solve(i + 1, next, next_tight, true),
+                    None => continue,
+                },
+            };
+            count += c;
+            sum += s + digit as u128 * self.powers[remaining - 1] * c;
+        }
+
+        if memoized {
+            self.memo.insert((i, state), (count, sum));
+        }
+        (count, sum)
+    }
+}
+
+/// Returns the number and the sum of the numbers in [0, n] accepted by the machine.
+fn count_and_sum<M: DigitMachine>(machine: &M, base: u32, n: u64) -> (u128, u128) {
+    assert!(base >= 2, "Digit DP: the base must be at least 2");
+    let digits = digits(n, base);
+    let mut powers = vec![1_u128; digits.len()];
+    for i in 1..powers.len() {
+        powers[i] = powers[i - 1] * base as u128;
+    }
+
+    let mut dp = DigitDp {
+        machine,
+        base,
+        digits,
+        powers,
+        memo: HashMap::new(),
+    };
+    dp.solve(0, machine.start(), true, false)
+}
+
+/// Counts the numbers in [l, r] accepted by the machine, reading their digits in the given base.
+///
+/// # Arguments
+///
+/// * `machine`: the property of the numbers
+/// * `base`: the base of the digits
+/// * `l`: the left endpoint of the range
+/// * `r`: the right endpoint of the range
+///
+/// returns: u128
+/// O(digits * states * base), the states being the ones reachable by the machine
+pub fn count_in_range<M: DigitMachine>(machine: &M, base: u32, l: u64, r: u64) -> u128 {
+    if l > r {
+        return 0;
+    }
+    let below = match l {
+        0 => 0,
+        _ => count_and_sum(machine, base, l - 1).0,
+    };
+    count_and_sum(machine, base, r).0 - below
+}
+
+/// Sums the numbers in [l, r] accepted by the machine, reading their digits in the given base.
+///
+/// # Arguments
+///
+/// * `machine`: the property of the numbers
+/// * `base`: the base of the digits
+/// * `l`: the left endpoint of the range
+/// * `r`: the right endpoint of the range
+///
+/// returns: u128
+/// O(digits * states * base), the states being the ones reachable by the machine
+pub fn sum_in_range<M: DigitMachine>(machine: &M, base: u32, l: u64, r: u64) -> u128 {
+    if l > r {
+        return 0;
+    }
+    let below = match l {
+        0 => 0,
+        _ => count_and_sum(machine, base, l - 1).1,
+    };
+    count_and_sum(machine, base, r).1 - below
+}
+
+/// Numbers without two consecutive zeros in their digits: the state is whether the last digit
+/// is 0. In base 2 it counts the strings of `zero_11_ss`, see `zero_11_ss_digit_dp`.
+pub struct NoConsecutiveZeros;
+
+impl DigitMachine for NoConsecutiveZeros {
+    type State = bool;
+
+    fn start(&self) -> bool {
+        false
+    }
+
+    fn step(&self, &last_zero: &bool, digit: u32) -> Option<bool> {
+        match (last_zero, digit) {
+            (true, 0) => None,
+            _ => Some(digit == 0),
+        }
+    }
+
+    fn accepts(&self, _: &bool) -> bool {
+        true
+    }
+}
+
+/// Numbers whose digit sum is a multiple of k and which have no digit equal to forbidden.
+#[cfg(test)]
+struct DigitSum {
+    k: u32,
+    forbidden: Option<u32>,
+}
+
+#[cfg(test)]
+impl DigitMachine for DigitSum {
+    type State = u32;
+
+    fn start(&self) -> u32 {
+        0
+    }
+
+    fn step(&self, sum: &u32, digit: u32) -> Option<u32> {
+        (Some(digit) != self.forbidden).then_some((sum + digit) % self.k)
+    }
+
+    fn accepts(&self, sum: &u32) -> bool {
+        *sum == 0
+    }
+}
+
+#[test]
+pub fn test_digit_dp_zero_11_ss() {
+    use crate::dynamic_programming::easy_problems::{zero_11_ss, zero_11_ss_digit_dp};
+
+    for n in 0..64 {
+        assert_eq!(zero_11_ss_digit_dp(n), zero_11_ss(n));
+    }
+    // in base 10 the numbers below 100 with a 0 are 10, 20, ..., 90 and 0 is allowed
+    assert_eq!(count_in_range(&NoConsecutiveZeros, 10, 0, 99), 100);
+    assert_eq!(count_in_range(&NoConsecutiveZeros, 10, 0, 1000), 991);
+}
+
+#[test]
+pub fn test_digit_dp_brute_force() {
+    let digit_sum = |mut x: u64, base: u64| {
+        let mut sum = 0;
+        while x > 0 {
+            sum += x % base;
+            x /= base;
+        }
+        sum
+    };
+    let has_digit = |mut x: u64, base: u64, d: u64| {
+        if x == 0 {
+            return d == 0;
+        }
+        while x > 0 {
+            if x % base == d {
+                return true;
+            }
+            x /= base;
+        }
+        false
+    };
+
+    for base in [2, 3, 10] {
+        for k in [1, 3, 7] {
+            for forbidden in [None, Some(0), Some(1)] {
+                let machine = DigitSum { k, forbidden };
+                let accepted = |x: u64| {
+                    digit_sum(x, base).is_multiple_of(k as u64)
+                        && forbidden.is_none_or(|d| !has_digit(x, base, d as u64))
+                };
+                for (l, r) in [(0, 0), (0, 100), (17, 523), (1000, 999), (64, 64)] {
+                    let numbers: Vec<u64> = (l..=r).filter(|&x| accepted(x)).collect();
+                    assert_eq!(
+                        count_in_range(&machine, base as u32, l, r),
+                        numbers.len() as u128
+                    );
+                    assert_eq!(
+                        sum_in_range(&machine, base as u32, l, r),
+                        numbers.iter().map(|&x| x as u128).sum::<u128>()
+                    );
+                }
+            }
+        }
+    }
+
+    // the whole range of u64, where the numbers without 0 digits in base 2 are 2^i - 1
+    let ones = DigitSum {
+        k: 1,
+        forbidden: Some(0),
+    };
+    assert_eq!(count_in_range(&ones, 2, 0, u64::MAX), 64);
+    assert_eq!(
+        sum_in_range(&ones, 2, 0, u64::MAX),
+        (0..=64).map(|i| (1_u128 << i) - 1).sum::<u128>()
+    );
+}
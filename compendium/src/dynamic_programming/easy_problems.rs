@@ -1,27 +1,40 @@
+use crate::dynamic_programming::digit_dp::{count_in_range, NoConsecutiveZeros};
+use crate::dynamic_programming::grid_paths::{best_path, Objective, RIGHT_DOWN};
+use crate::dynamic_programming::interval_dp::rod_cutting_with_cuts;
+use crate::dynamic_programming::linear_recurrence::nth_term_matrix;
+use crate::utilities::matrix::{Arithmetic, Matrix};
 
-/// Computes the nth fibonacci number.
+
+/// Computes the nth fibonacci number, as the top right entry of [[1, 1], [1, 0]]^n.
+/// Panics if it does not fit in a usize, see `fibonacci_mod` for larger n.
 ///
 /// # Arguments
 ///
 /// * `n`: the index of the fibonacci number to find
 ///
 /// returns: usize
-/// \theta(n)
+/// \theta(\log n)
 fn fibonacci(n: usize) -> usize {
-    if n <= 1 {
-        return n;
-    }
-
-    let mut n2 = n - 2;
-    let mut n1 = n - 1;
-    let mut res = 0;
-    for _ in 2..=n {
-        res = n1 + n2;
-        n2 = n1;
-        n1 = res;
-    }
+    // F(94) does not fit in a u64, and checking first keeps the power from overflowing its
+    // u128 entries when n is much larger
+    assert!(n <= 93, "Fibonacci: the result overflows a usize");
+    // the power also holds F(n + 1), computed in u128 so that F(n) can be the largest in a usize
+    let q = Matrix::from_rows(vec![vec![1_u128, 1], vec![1, 0]]);
+    let f = *q.pow(n as u64, &Arithmetic).get(0, 1);
+    usize::try_from(f).expect("Fibonacci: the result overflows a usize")
+}
 
-    res
+/// Computes the nth fibonacci number modulo p.
+///
+/// # Arguments
+///
+/// * `n`: the index of the fibonacci number to find
+/// * `p`: the modulus
+///
+/// returns: u64
+/// \theta(\log n)
+pub fn fibonacci_mod(n: u64, p: u64) -> u64 {
+    nth_term_matrix(&[1, 1], &[0, 1], n, p)
 }
 
 
@@ -35,20 +48,14 @@ fn fibonacci(n: usize) -> usize {
 /// returns: usize
 /// \theta(n^2)
 pub fn rod_cutting(prices: Vec<usize>, n: usize) -> usize {
-    let mut dp = vec![0; n + 1];
-    for i in 1..=n {
-        let mut max_val = 0;
-        for j in 0..i {
-            max_val = max_val.max(prices[j] + dp[i - j - 1]);
-        }
-        dp[i] = max_val;
-    }
-    dp[n]
+    let prices: Vec<i64> = prices.into_iter().map(|p| p as i64).collect();
+    rod_cutting_with_cuts(&prices, n, 0).0 as usize
 }
 
 
 /// Given an n by m matrix, find the minimum cost path from the top left element
-/// to the bottom right element.
+/// to the bottom right element, moving only right and down. The path is summed in
+/// i64, panics if its cost does not fit in an i32.
 ///
 /// # Arguments
 ///
@@ -57,20 +64,14 @@ pub fn rod_cutting(prices: Vec<usize>, n: usize) -> usize {
 /// returns: i32
 /// \theta(m * n)
 pub fn minimum_cost_path(a: Vec<Vec<i32>>) -> i32 {
-    let n = a.len();
-    assert_ne!(n, 0);
-    let m = a[0].len();
-
-    let mut t = vec![vec![i32::MAX; m + 1]; n + 1];
-    t[0][1] = 0;
-    t[1][0] = 0;
-
-    for i in 1..=n {
-        for j in 1..=m {
-            t[i][j] = a[i - 1][j - 1] + t[i - 1][j].min(t[i][j - 1]);
-        }
-    }
-    t[n][m]
+    assert_ne!(a.len(), 0);
+    let grid: Vec<Vec<Option<i64>>> = a
+        .iter()
+        .map(|row| row.iter().map(|&x| Some(x as i64)).collect())
+        .collect();
+
+    let (cost, _) = best_path(&grid, RIGHT_DOWN, Objective::MinCost).unwrap();
+    i32::try_from(cost).expect("Minimum Cost Path: the cost overflows an i32")
 }
 
 /// Counts the number of ways to construct a string of 1s and 0s such that there
@@ -81,23 +82,41 @@ pub fn minimum_cost_path(a: Vec<Vec<i32>>) -> i32 {
 /// * `n`: the length of the string
 ///
 /// returns: usize
-/// \theta(n)
+/// \theta(\log n)
 pub fn zero_11_ss(n: usize) -> usize {
+    // a valid string ends either in 1 or in 10, so the counts follow the fibonacci numbers
+    fibonacci(n + 2)
+}
 
-    if n <= 1 {
-        return n + 1;
-    }
-
-    let mut n2 = n - 2;
-    let mut n1 = n - 1;
-    let mut res = 0;
-    for _ in 2..=n {
-        res = n1 + n2;
-        n2 = n1;
-        n1 = res;
-    }
+/// Counts the number of ways to construct a string of 1s and 0s such that there
+/// are no consecutive zeros, with a digit DP: a string s of n bits is valid iff
+/// the binary number 1s, in [2^n, 2^(n+1) - 1], has no two consecutive zeros.
+/// Panics if n >= 64, as the numbers would not fit in a u64.
+///
+/// # Arguments
+///
+/// * `n`: the length of the string
+///
+/// returns: usize
+/// \theta(n)
+pub fn zero_11_ss_digit_dp(n: usize) -> usize {
+    assert!(n < 64, "Zero 11 SS: n + 1 bits must fit in a u64");
+    let count = count_in_range(&NoConsecutiveZeros, 2, 1 << n, u64::MAX >> (63 - n));
+    usize::try_from(count).expect("Zero 11 SS: the count does not fit in a usize")
+}
 
-    res
+/// Counts modulo p the number of ways to construct a string of 1s and 0s such
+/// that there are no consecutive zeros.
+///
+/// # Arguments
+///
+/// * `n`: the length of the string
+/// * `p`: the modulus
+///
+/// returns: u64
+/// \theta(\log n)
+pub fn zero_11_ss_mod(n: u64, p: u64) -> u64 {
+    nth_term_matrix(&[1, 1], &[1, 2], n, p)
 }
 
 
@@ -107,6 +126,18 @@ pub fn test_fibonacci() {
     assert_eq!(fibonacci(6), 8);
     assert_eq!(fibonacci(7), 13);
     assert_eq!(fibonacci(15), 610);
+    assert_eq!(fibonacci(0), 0);
+    assert_eq!(fibonacci(1), 1);
+    assert_eq!(fibonacci(93), 12_200_160_415_121_876_738);
+    let p = 1_000_000_007;
+    assert_eq!(fibonacci_mod(93, p), 12_200_160_415_121_876_738 % p);
+    assert_eq!(fibonacci_mod(100, u64::MAX), 3_736_710_778_780_434_390);
+}
+
+#[test]
+#[should_panic(expected = "Fibonacci: the result overflows a usize")]
+pub fn test_fibonacci_overflow() {
+    fibonacci(200);
 }
 
 #[test]
@@ -123,9 +154,24 @@ pub fn test_minimum_cost_path() {
         vec![4, 2, 1]
     ];
     assert_eq!(minimum_cost_path(a), 7);
+    // negative costs are allowed
+    assert_eq!(minimum_cost_path(vec![vec![-1, -2], vec![-3, -4]]), -8);
+}
+
+#[test]
+#[should_panic(expected = "Minimum Cost Path: the cost overflows an i32")]
+pub fn test_minimum_cost_path_overflow() {
+    minimum_cost_path(vec![vec![i32::MAX, i32::MAX]]);
 }
 
 #[test]
 pub fn test_zero_11_ss() {
     assert_eq!(zero_11_ss(3), 5);
+    assert_eq!(zero_11_ss(0), 1);
+    assert_eq!(zero_11_ss(1), 2);
+    assert_eq!(zero_11_ss(4), 8);
+    let p = 1_000_000_007;
+    for n in 0..60 {
+        assert_eq!(zero_11_ss_mod(n, p), zero_11_ss(n as usize) as u64 % p);
+    }
 }
@@ -0,0 +1,398 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// A grid is given by rows of cells, None being an obstacle and Some(v) the cost (or reward)
+// of passing through the cell. Paths go from the top left cell to the bottom right one, and
+// their value is the sum over all the visited cells, both ends included.
+
+/// A move as the (row, column) offset to the next cell.
+pub type Move = (isize, isize);
+
+pub const RIGHT_DOWN: &[Move] = &[(0, 1), (1, 0)];
+pub const RIGHT_DOWN_DIAGONAL: &[Move] = &[(0, 1), (1, 0), (1, 1)];
+pub const FOUR_DIRECTIONS: &[Move] = &[(0, 1), (1, 0), (0, -1), (-1, 0)];
+pub const EIGHT_DIRECTIONS: &[Move] = &[
+    (0, 1),
+    (1, 0),
+    (0, -1),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Whether the best path minimizes the total cost or maximizes the total reward.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+    MinCost,
+    MaxReward,
+}
+
+impl Objective {
+    /// Returns whether the value a is strictly better than b.
+    fn improves(self, a: i64, b: i64) -> bool {
+        match self {
+            Objective::MinCost => a < b,
+            Objective::MaxReward => a > b,
+        }
+    }
+}
+
+/// Returns whether every move goes down, right or both, so that the cells in row-major order
+/// are a topological order of the moves and a single DP pass is enough.
+fn is_monotone(moves: &[Move]) -> bool {
+    moves
+        .iter()
+        .all(|&(di, dj)| di >= 0 && dj >= 0 && (di, dj) != (0, 0))
+}
+
+/// Returns the cell reached from (i, j) with the given offset, if inside the grid.
+fn step<T>(grid: &[Vec<T>], (i, j): (usize, usize), (di, dj): Move) -> Option<(usize, usize)> {
+    let i = i.checked_add_signed(di)?;
+    let j = j.checked_add_signed(dj)?;
+    (i < grid.len() && j < grid[i].len()).then_some((i, j))
+}
+
+/// Follows the parents back from the bottom right cell.
+fn reconstruct(
+    parents: &[Vec<Option<(usize, usize)>>],
+    target: (usize, usize),
+) -> Vec<(usize, usize)> {
+    let mut path = vec![target];
+    let mut cell = target;
+    while let Some(previous) = parents[cell.0][cell.1] {
+        path.push(previous);
+        cell = previous;
+    }
+    path.reverse();
+    path
+}
+
+fn assert_valid<T>(grid: &[Vec<T>]) -> (usize, usize) {
+    let n = grid.len();
+    assert_ne!(n, 0, "Grid Paths: the grid is empty");
+    let m = grid[0].len();
+    assert!(
+        m > 0 && grid.iter().all(|row| row.len() == m),
+        "Grid Paths: the rows must be non empty and of the same length"
+    );
+    (n, m)
+}
+
+/// Returns the value of the best path from the top left to the bottom right cell together with
+/// its cells, or None if the bottom right cell can not be reached. Moves going only down and
+/// right are solved with a DP over the cells in row-major order, the others with Dijkstra,
+/// which needs non-negative costs and can only minimize.
+///
+/// # Arguments
+///
+/// * `grid`: the costs or rewards of the cells, None for the obstacles
+/// * `moves`: the allowed moves
+/// * `objective`: whether to minimize the cost or maximize the reward
+///
+/// returns: Option<(i64, Vec<(usize, usize)>)>
+/// \theta(n * m * |moves|) for down-right moves, O(n * m * |moves| \log(n * m)) otherwise
+pub fn best_path(
+    grid: &[Vec<Option<i64>>],
+    moves: &[Move],
+    objective: Objective,
+) -> Option<(i64, Vec<(usize, usize)>)> {
+    if is_monotone(moves) {
+        return dp_path(grid, moves, objective);
+    }
+    assert_eq!(
+        objective,
+        Objective::MinCost,
+        "Grid Paths: maximizing with moves in every direction is not well defined"
+    );
+    dijkstra_path(grid, moves)
+}
+
+/// The DP of `best_path` for moves going down and right: the best path to a cell extends the
+/// best path to one of the cells it can be reached from, all of which come earlier in
+/// row-major order.
+fn dp_path(
+    grid: &[Vec<Option<i64>>],
+    moves: &[Move],
+    objective: Objective,
+) -> Option<(i64, Vec<(usize, usize)>)> {
+    let (n, m) = assert_valid(grid);
+
+    let mut best: Vec<Vec<Option<i64>>> = vec![vec![None; m]; n];
+    let mut parents = vec![vec![None; m]; n];
+    best[0][0] = grid[0][0];
+
+    for i in 0..n {
+        for j in 0..m {
+            let Some(value) = grid[i][j] else {
+                continue;
+            };
+            for &(di, dj) in moves {
+                let Some((pi, pj)) = step(grid, (i, j), (-di, -dj)) else {
+                    continue;
+                };
+                if let Some(previous) = best[pi][pj] {
+                    let candidate = previous + value;
+                    if best[i][j].is_none_or(|b| objective.improves(candidate, b)) {
+                        best[i][j] = Some(candidate);
+                        parents[i][j] = Some((pi, pj));
+                    }
+                }
+            }
+        }
+    }
+
+    let value = best[n - 1][m - 1]?;
+    Some((value, reconstruct(&parents, (n - 1, m - 1))))
+}
+
+/// Returns the minimum cost of a path from the top left to the bottom right cell together with
+/// its cells, or None if the bottom right cell can not be reached, with Dijkstra on the graph
+/// of the cells, entering a cell costing its value. Works with any move set.
+///
+/// # Arguments
+///
+/// * `grid`: the non-negative costs of the cells, None for the obstacles
+/// * `moves`: the allowed moves
+///
+/// returns: Option<(i64, Vec<(usize, usize)>)>
+/// O(n * m * |moves| \log(n * m))
+pub fn dijkstra_path(
+    grid: &[Vec<Option<i64>>],
+    moves: &[Move],
+) -> Option<(i64, Vec<(usize, usize)>)> {
+    let (n, m) = assert_valid(grid);
+    assert!(
+        grid.iter().flatten().flatten().all(|&cost| cost >= 0),
+        "Grid Paths: Dijkstra needs non-negative costs"
+    );
+
+    let mut distance: Vec<Vec<Option<i64>>> = vec![vec![None; m]; n];
+    let mut parents = vec![vec![None; m]; n];
+    let mut queue = BinaryHeap::new();
+    if let Some(cost) = grid[0][0] {
+        distance[0][0] = Some(cost);
+        queue.push(Reverse((cost, (0, 0))));
+    }
+
+    while let Some(Reverse((d, (i, j)))) = queue.pop() {
+        if distance[i][j] != Some(d) {
+            continue;
+        }
+        for &offset in moves {
+            let Some((ni, nj)) = step(grid, (i, j), offset) else {
+                continue;
+            };
+            if let Some(cost) = grid[ni][nj] {
+                if distance[ni][nj].is_none_or(|current| d + cost < current) {
+                    distance[ni][nj] = Some(d + cost);
+                    parents[ni][nj] = Some((i, j));
+                    queue.push(Reverse((d + cost, (ni, nj))));
+                }
+            }
+        }
+    }
+
+    let value = distance[n - 1][m - 1]?;
+    Some((value, reconstruct(&parents, (n - 1, m - 1))))
+}
+
+/// Counts modulo p the paths from the top left to the bottom right cell avoiding the
+/// obstacles. The moves must go down and right, otherwise the number of paths may be infinite.
+///
+/// # Arguments
+///
+/// * `blocked`: whether each cell is an obstacle
+/// * `moves`: the allowed moves, going down and right
+/// * `p`: the modulus
+///
+/// returns: u64
+/// \theta(n * m * |moves|)
+pub fn count_paths(blocked: &[Vec<bool>], moves: &[Move], p: u64) -> u64 {
+    let (n, m) = assert_valid(blocked);
+    assert!(
+        is_monotone(moves),
+        "Grid Paths: paths can only be counted with moves going down and right"
+    );
+    assert!(p > 0, "Grid Paths: the modulus must be positive");
+
+    let mut ways = vec![vec![0; m]; n];
+    for i in 0..n {
+        for j in 0..m {
+            if blocked[i][j] {
+                continue;
+            }
+            if (i, j) == (0, 0) {
+                ways[i][j] = 1 % p;
+                continue;
+            }
+            for &(di, dj) in moves {
+                if let Some((pi, pj)) = step(blocked, (i, j), (-di, -dj)) {
+                    ways[i][j] = (ways[i][j] + ways[pi][pj]) % p;
+                }
+            }
+        }
+    }
+    ways[n - 1][m - 1]
+}
+
+/// Checks that the path goes from corner to corner with allowed moves avoiding the obstacles,
+/// and returns its value.
+#[cfg(test)]
+fn path_value(grid: &[Vec<Option<i64>>], moves: &[Move], path: &[(usize, usize)]) -> i64 {
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&(grid.len() - 1, grid[0].len() - 1)));
+    for w in path.windows(2) {
+        assert!(moves
+            .iter()
+            .any(|&offset| step(grid, w[0], offset) == Some(w[1])));
+    }
+    path.iter().map(|&(i, j)| grid[i][j].unwrap()).sum()
+}
+
+/// Enumerates the simple paths from corner to corner, returning the minimum and maximum value
+/// and their number.
+#[cfg(test)]
+fn brute_force(grid: &[Vec<Option<i64>>], moves: &[Move]) -> (Option<i64>, Option<i64>, u64) {
+    fn rec(
+        grid: &[Vec<Option<i64>>],
+        moves: &[Move],
+        cell: (usize, usize),
+        value: i64,
+        visited: &mut Vec<Vec<bool>>,
+        result: &mut (Option<i64>, Option<i64>, u64),
+    ) {
+        if cell == (grid.len() - 1, grid[0].len() - 1) {
+            result.0 = Some(result.0.map_or(value, |v| v.min(value)));
+            result.1 = Some(result.1.map_or(value, |v| v.max(value)));
+            result.2 += 1;
+            return;
+        }
+        for &offset in moves {
+            if let Some((i, j)) = step(grid, cell, offset) {
+                if let (Some(cost), false) = (grid[i][j], visited[i][j]) {
+                    visited[i][j] = true;
+                    rec(grid, moves, (i, j), value + cost, visited, result);
+                    visited[i][j] = false;
+                }
+            }
+        }
+    }
+
+    let mut result = (None, None, 0);
+    if let Some(cost) = grid[0][0] {
+        let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
+        visited[0][0] = true;
+        rec(grid, moves, (0, 0), cost, &mut visited, &mut result);
+    }
+    result
+}
+
+#[test]
+pub fn test_best_path() {
+    let grid = vec![
+        vec![Some(1), Some(3), Some(1)],
+        vec![Some(1), Some(5), Some(1)],
+        vec![Some(4), Some(2), Some(1)],
+    ];
+    assert_eq!(
+        best_path(&grid, RIGHT_DOWN, Objective::MinCost),
+        Some((7, vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]))
+    );
+    assert_eq!(
+        best_path(&grid, RIGHT_DOWN, Objective::MaxReward).map(|(v, _)| v),
+        Some(12)
+    );
+    assert_eq!(
+        best_path(&grid, RIGHT_DOWN_DIAGONAL, Objective::MinCost),
+        Some((5, vec![(0, 0), (1, 0), (2, 1), (2, 2)]))
+    );
+
+    // a wall leaves only a snake-shaped path, which needs to go left and up
+    let x = None;
+    let maze = vec![
+        vec![Some(1), Some(1), Some(1), Some(1)],
+        vec![x, x, x, Some(1)],
+        vec![Some(1), Some(1), Some(1), Some(1)],
+        vec![Some(1), x, x, x],
+        vec![Some(1), Some(1), Some(1), Some(1)],
+    ];
+    assert_eq!(best_path(&maze, RIGHT_DOWN, Objective::MinCost), None);
+    let (cost, path) = best_path(&maze, FOUR_DIRECTIONS, Objective::MinCost).unwrap();
+    assert_eq!(cost, 14);
+    assert_eq!(path_value(&maze, FOUR_DIRECTIONS, &path), 14);
+    let blocked: Vec<Vec<bool>> = maze
+        .iter()
+        .map(|row| row.iter().map(Option::is_none).collect())
+        .collect();
+    assert_eq!(count_paths(&blocked, RIGHT_DOWN, 7), 0);
+}
+
+#[test]
+pub fn test_best_path_brute_force() {
+    let mut rng = Lcg::new(7);
+    for _ in 0..40 {
+        let mut random = || rng.next_u64();
+        let (n, m) = (1 + random() as usize % 4, 1 + random() as usize % 4);
+        let mut grid: Vec<Vec<Option<i64>>> = (0..n)
+            .map(|_| {
+                (0..m)
+                    .map(|_| match random() % 5 {
+                        0 => None,
+                        r => Some(r as i64 * 3 - 5 + random() as i64 % 4),
+                    })
+                    .collect()
+            })
+            .collect();
+        grid[0][0] = Some(1);
+        let blocked: Vec<Vec<bool>> = grid
+            .iter()
+            .map(|row| row.iter().map(Option::is_none).collect())
+            .collect();
+
+        for moves in [RIGHT_DOWN, RIGHT_DOWN_DIAGONAL] {
+            let (min, max, count) = brute_force(&grid, moves);
+            for (objective, expected) in [(Objective::MinCost, min), (Objective::MaxReward, max)] {
+                let result = best_path(&grid, moves, objective);
+                assert_eq!(result.as_ref().map(|(v, _)| *v), expected);
+                if let Some((value, path)) = result {
+                    assert_eq!(path_value(&grid, moves, &path), value);
+                }
+            }
+            assert_eq!(count_paths(&blocked, moves, 1_000_000_007), count);
+            assert_eq!(count_paths(&blocked, moves, 3), count % 3);
+        }
+
+        // Dijkstra needs non-negative costs
+        let costs: Vec<Vec<Option<i64>>> = grid
+            .iter()
+            .map(|row| row.iter().map(|c| c.map(i64::abs)).collect())
+            .collect();
+        for moves in [RIGHT_DOWN, FOUR_DIRECTIONS, EIGHT_DIRECTIONS] {
+            let (min, _, _) = brute_force(&costs, moves);
+            let result = dijkstra_path(&costs, moves);
+            assert_eq!(result.as_ref().map(|(v, _)| *v), min);
+            if let Some((value, path)) = result {
+                assert_eq!(path_value(&costs, moves, &path), value);
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_count_paths() {
+    // binomial(n + m - 2, n - 1) paths in an empty grid
+    let empty = vec![vec![false; 6]; 5];
+    assert_eq!(count_paths(&empty, RIGHT_DOWN, 1_000_000_007), 126);
+    // Delannoy numbers with diagonal moves
+    assert_eq!(count_paths(&empty, RIGHT_DOWN_DIAGONAL, 1_000_000_007), 681);
+
+    let mut blocked = vec![vec![false; 3]; 3];
+    blocked[1][1] = true;
+    assert_eq!(count_paths(&blocked, RIGHT_DOWN, 1_000_000_007), 2);
+    blocked[0][0] = true;
+    assert_eq!(count_paths(&blocked, RIGHT_DOWN, 1_000_000_007), 0);
+}
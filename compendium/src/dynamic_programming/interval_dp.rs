@@ -0,0 +1,392 @@
+use crate::dynamic_programming::optimizations::knuth_yao;
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+
+/// Rod cutting where every cut costs cut_cost: returns the best revenue for a rod of length n
+/// together with the lengths of the pieces. prices[i] is the price of a piece of length i + 1.
+///
+/// # Arguments
+///
+/// * `prices`: the prices of the pieces, at least n of them
+/// * `n`: the length of the rod
+/// * `cut_cost`: the cost of every cut
+///
+/// returns: (i64, Vec<usize>)
+/// \theta(n^2)
+pub fn rod_cutting_with_cuts(prices: &[i64], n: usize, cut_cost: i64) -> (i64, Vec<usize>) {
+    assert!(prices.len() >= n, "Rod Cutting: missing prices");
+
+    // best[i] is the revenue of a rod of length i, first[i] the length of its first piece
+    let mut best = vec![0; n + 1];
+    let mut first = vec![0; n + 1];
+    for i in 1..=n {
+        best[i] = i64::MIN;
+        for j in 1..=i {
+            let rest = match i - j {
+                0 => 0,
+                r => best[r] - cut_cost,
+            };
+            if prices[j - 1] + rest > best[i] {
+                best[i] = prices[j - 1] + rest;
+                first[i] = j;
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut i = n;
+    while i > 0 {
+        pieces.push(first[i]);
+        i -= first[i];
+    }
+    (best[n], pieces)
+}
+
+/// Cutting a stick of the given length at the given positions, where every cut costs the
+/// length of the piece being cut. Returns the minimum cost and the positions in the order
+/// they are cut. Merging the pieces between consecutive positions costs the same, and the
+/// lengths satisfy the quadrangle inequality, so the Knuth-Yao speedup applies.
+///
+/// # Arguments
+///
+/// * `length`: the length of the stick
+/// * `cuts`: the positions of the cuts, distinct and in (0, length)
+///
+/// returns: (u64, Vec<u64>)
+/// \theta(m^2), m being the number of cuts
+pub fn stick_cutting(length: u64, cuts: &[u64]) -> (u64, Vec<u64>) {
+    let mut positions = vec![0];
+    positions.extend_from_slice(cuts);
+    positions.push(length);
+    positions.sort_unstable();
+    assert!(
+        positions.windows(2).all(|w| w[0] < w[1]),
+        "Stick Cutting: the cuts must be distinct and inside the stick"
+    );
+
+    // piece i lies between positions[i] and positions[i + 1]
+    let pieces = positions.len() - 1;
+    let (cost, opt) = knuth_yao(pieces, |i, j| (positions[j + 1] - positions[i]) as i64);
+
+    let mut order = Vec::new();
+    let mut stack = vec![(0, pieces - 1)];
+    while let Some((i, j)) = stack.pop() {
+        if i < j {
+            let k = opt[i][j];
+            order.push(positions[k + 1]);
+            stack.push((k + 1, j));
+            stack.push((i, k));
+        }
+    }
+    (cost as u64, order)
+}
+
+/// Matrix chain multiplication: returns the minimum number of scalar multiplications to
+/// compute the product of the matrices, the ith being dims[i] x dims[i + 1], and an optimal
+/// parenthesization such as ((A0A1)A2).
+///
+/// # Arguments
+///
+/// * `dims`: the dimensions of the matrices
+///
+/// returns: (u64, String)
+/// \theta(n^3)
+pub fn matrix_chain(dims: &[u64]) -> (u64, String) {
+    assert!(
+        dims.len() >= 2,
+        "Matrix Chain: at least one matrix is needed"
+    );
+    let n = dims.len() - 1;
+
+    // cost[i][j] is the cost of the product of the matrices i..=j, split[i][j] the last product
+    let mut cost = vec![vec![0; n]; n];
+    let mut split = vec![vec![0; n]; n];
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length - 1;
+            cost[i][j] = u64::MAX;
+            for k in i..j {
+                let candidate = cost[i][k] + cost[k + 1][j] + dims[i] * dims[k + 1] * dims[j + 1];
+                if candidate < cost[i][j] {
+                    cost[i][j] = candidate;
+                    split[i][j] = k;
+                }
+            }
+        }
+    }
+
+    fn parenthesize(split: &[Vec<usize>], i: usize, j: usize, out: &mut String) {
+        if i == j {
+            out.push_str(&format!("A{}", i));
+            return;
+        }
+        out.push('(');
+        parenthesize(split, i, split[i][j], out);
+        parenthesize(split, split[i][j] + 1, j, out);
+        out.push(')');
+    }
+
+    let mut parenthesization = String::new();
+    parenthesize(&split, 0, n - 1, &mut parenthesization);
+    (cost[0][n - 1], parenthesization)
+}
+
+/// Optimal binary search tree: given the access frequencies of sorted keys, returns the
+/// minimum of the sum of frequency times depth (the root having depth 1) and the parent of
+/// every key in an optimal tree, None for the root. The best roots are monotone, as in
+/// Knuth's optimization: root(i, j - 1) <= root(i, j) <= root(i + 1, j).
+///
+/// # Arguments
+///
+/// * `frequencies`: the access frequencies of the keys
+///
+/// returns: (u64, Vec<Option<usize>>)
+/// \theta(n^2)
+pub fn optimal_bst(frequencies: &[u64]) -> (u64, Vec<Option<usize>>) {
+    let n = frequencies.len();
+    let mut prefix = vec![0; n + 1];
+    for (i, f) in frequencies.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + f;
+    }
+
+    // cost[i][j] and root[i][j] for the keys in [i, j), the empty ranges costing 0
+    let mut cost = vec![vec![0; n + 1]; n + 1];
+    let mut root = vec![vec![0; n + 1]; n + 1];
+    for i in 0..n {
+        cost[i][i + 1] = frequencies[i];
+        root[i][i + 1] = i;
+    }
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length;
+            cost[i][j] = u64::MAX;
+            for r in root[i][j - 1]..=root[i + 1][j] {
+                let candidate = cost[i][r] + cost[r + 1][j];
+                if candidate < cost[i][j] {
+                    cost[i][j] = candidate;
+                    root[i][j] = r;
+                }
+            }
+            cost[i][j] += prefix[j] - prefix[i];
+        }
+    }
+
+    let mut parents = vec![None; n];
+    let mut stack = vec![(0, n, None)];
+    while let Some((i, j, parent)) = stack.pop() {
+        if i < j {
+            let r = root[i][j];
+            parents[r] = parent;
+            stack.push((i, r, Some(r)));
+            stack.push((r + 1, j, Some(r)));
+        }
+    }
+    (cost[0][n], parents)
+}
+
+/// Burst balloons: bursting balloon i gives the product of its value and the values of its
+/// current neighbours, 1 past the ends. Returns the maximum total and an order of the
+/// bursts achieving it. The last balloon burst in an interval splits it into two independent
+/// intervals, with fixed neighbours.
+///
+/// # Arguments
+///
+/// * `values`: the values of the balloons
+///
+/// returns: (i64, Vec<usize>)
+/// \theta(n^3)
+pub fn burst_balloons(values: &[i64]) -> (i64, Vec<usize>) {
+    let n = values.len();
+    let mut padded = vec![1];
+    padded.extend_from_slice(values);
+    padded.push(1);
+
+    // best[i][j] is the total of bursting every balloon strictly between i and j in padded,
+    // last[i][j] the one burst last
+    let mut best = vec![vec![0; n + 2]; n + 2];
+    let mut last = vec![vec![0; n + 2]; n + 2];
+    for length in 2..n + 2 {
+        for i in 0..n + 2 - length {
+            let j = i + length;
+            best[i][j] = i64::MIN;
+            for k in i + 1..j {
+                let candidate = best[i][k] + best[k][j] + padded[i] * padded[k] * padded[j];
+                if candidate > best[i][j] {
+                    best[i][j] = candidate;
+                    last[i][j] = k;
+                }
+            }
+        }
+    }
+
+    fn order(last: &[Vec<usize>], i: usize, j: usize, out: &mut Vec<usize>) {
+        if i + 1 < j {
+            let k = last[i][j];
+            order(last, i, k, out);
+            order(last, k, j, out);
+            out.push(k - 1);
+        }
+    }
+
+    let mut bursts = Vec::with_capacity(n);
+    order(&last, 0, n + 1, &mut bursts);
+    (best[0][n + 1], bursts)
+}
+
+#[test]
+pub fn test_rod_cutting_with_cuts() {
+    let prices = [1, 5, 8, 9, 10, 17, 17, 20];
+    let (revenue, pieces) = rod_cutting_with_cuts(&prices, 8, 0);
+    assert_eq!(revenue, 22);
+    assert_eq!(pieces.iter().sum::<usize>(), 8);
+    assert_eq!(pieces.iter().map(|&l| prices[l - 1]).sum::<i64>(), 22);
+
+    // with expensive cuts the whole rod is better than 2 + 6
+    assert_eq!(rod_cutting_with_cuts(&prices, 8, 3), (20, vec![8]));
+    assert_eq!(rod_cutting_with_cuts(&prices, 8, 1).0, 21);
+    assert_eq!(rod_cutting_with_cuts(&prices, 0, 1), (0, vec![]));
+
+    // brute force over the compositions of n
+    for n in 1..=prices.len() {
+        for cut_cost in 0..4 {
+            let mut best = i64::MIN;
+            for mask in 0..1_u32 << (n - 1) {
+                let mut revenue = -cut_cost * mask.count_ones() as i64;
+                let mut start = 0;
+                for end in 1..=n {
+                    if end == n || mask >> (end - 1) & 1 == 1 {
+                        revenue += prices[end - start - 1];
+                        start = end;
+                    }
+                }
+                best = best.max(revenue);
+            }
+            let (revenue, pieces) = rod_cutting_with_cuts(&prices, n, cut_cost);
+            assert_eq!(revenue, best);
+            let total: i64 = pieces.iter().map(|&l| prices[l - 1]).sum();
+            assert_eq!(total - cut_cost * (pieces.len() as i64 - 1), best);
+        }
+    }
+}
+
+#[test]
+pub fn test_stick_cutting() {
+    assert_eq!(stick_cutting(7, &[1, 3, 4, 5]).0, 16);
+    assert_eq!(stick_cutting(9, &[5, 6, 1, 4, 2]).0, 22);
+    assert_eq!(stick_cutting(10, &[]), (0, vec![]));
+
+    let (cost, order) = stick_cutting(7, &[1, 3, 4, 5]);
+    // replay the cuts
+    let mut pieces = vec![(0, 7)];
+    let mut total = 0;
+    for &cut in order.iter() {
+        let index = pieces
+            .iter()
+            .position(|&(a, b)| a < cut && cut < b)
+            .unwrap();
+        let (a, b) = pieces.remove(index);
+        total += b - a;
+        pieces.push((a, cut));
+        pieces.push((cut, b));
+    }
+    assert_eq!((total, order.len()), (cost, 4));
+}
+
+#[test]
+pub fn test_matrix_chain() {
+    assert_eq!(
+        matrix_chain(&[10, 30, 5, 60]),
+        (4500, "((A0A1)A2)".to_string())
+    );
+    assert_eq!(matrix_chain(&[40, 20, 30, 10, 30]).0, 26000);
+    assert_eq!(matrix_chain(&[10, 20, 30, 40, 30]).0, 30000);
+    assert_eq!(
+        matrix_chain(&[30, 35, 15, 5, 10, 20, 25]),
+        (15125, "((A0(A1A2))((A3A4)A5))".to_string())
+    );
+    assert_eq!(matrix_chain(&[3, 4]), (0, "A0".to_string()));
+}
+
+#[test]
+pub fn test_optimal_bst() {
+    assert_eq!(
+        optimal_bst(&[34, 8, 50]),
+        (142, vec![Some(2), Some(0), None])
+    );
+    assert_eq!(optimal_bst(&[]), (0, vec![]));
+
+    // naive O(n^3) DP, and the depths of the returned tree
+    let mut rng = Lcg::new(11);
+    for n in 1..25 {
+        let frequencies: Vec<u64> = (0..n).map(|_| rng.below(30)).collect();
+        let mut cost = vec![vec![0; n + 1]; n + 1];
+        for length in 1..=n {
+            for i in 0..=n - length {
+                let j = i + length;
+                let weight: u64 = frequencies[i..j].iter().sum();
+                cost[i][j] = (i..j).map(|r| cost[i][r] + cost[r + 1][j]).min().unwrap() + weight;
+            }
+        }
+
+        let (total, parents) = optimal_bst(&frequencies);
+        assert_eq!(total, cost[0][n]);
+        let depth = |mut i: usize| {
+            let mut d = 1;
+            while let Some(p) = parents[i] {
+                i = p;
+                d += 1;
+            }
+            d
+        };
+        let tree_cost: u64 = (0..n).map(|i| frequencies[i] * depth(i)).sum();
+        assert_eq!(tree_cost, total);
+        assert_eq!(parents.iter().filter(|p| p.is_none()).count(), 1);
+    }
+}
+
+#[test]
+pub fn test_burst_balloons() {
+    assert_eq!(burst_balloons(&[3, 1, 5, 8]), (167, vec![1, 2, 0, 3]));
+    assert_eq!(burst_balloons(&[1, 5]), (10, vec![0, 1]));
+    assert_eq!(burst_balloons(&[]), (0, vec![]));
+
+    // brute force over the orders, replaying the returned one
+    let replay = |values: &[i64], order: &[usize]| {
+        let mut alive: Vec<usize> = (0..values.len()).collect();
+        let mut total = 0;
+        for &b in order {
+            let position = alive.iter().position(|&i| i == b).unwrap();
+            let left = position.checked_sub(1).map_or(1, |p| values[alive[p]]);
+            let right = alive.get(position + 1).map_or(1, |&i| values[i]);
+            total += left * values[b] * right;
+            alive.remove(position);
+        }
+        total
+    };
+    fn permutations(n: usize, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        if prefix.len() == n {
+            out.push(prefix.clone());
+        }
+        for i in 0..n {
+            if !prefix.contains(&i) {
+                prefix.push(i);
+                permutations(n, prefix, out);
+                prefix.pop();
+            }
+        }
+    }
+
+    let values = [2, 0, 4, 3, 1, 5];
+    for n in 1..=values.len() {
+        let mut orders = Vec::new();
+        permutations(n, &mut Vec::new(), &mut orders);
+        let best = orders
+            .iter()
+            .map(|o| replay(&values[..n], o))
+            .max()
+            .unwrap();
+        let (total, order) = burst_balloons(&values[..n]);
+        assert_eq!(total, best);
+        assert_eq!(replay(&values[..n], &order), best);
+    }
+}
@@ -1,86 +1,447 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::cmp::Ordering;
+use std::ops::Add;
+
+/// 0/1 knapsack: given the values and the weights of n objects, returns the maximum value of
+/// a subset of the objects with total weight at most the capacity.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: i32
+/// \theta(n * capacity) time, \theta(capacity) memory
 pub fn knapsack(v: Vec<i32>, w: Vec<usize>, capacity: usize) -> i32 {
-    let n = v.len();
-    assert_eq!(n, w.len());
+    zero_one_knapsack(&v, &w, capacity)
+}
+
+/// 0/1 knapsack on a single rolling row: the capacities are visited from the largest, so every
+/// object is counted at most once.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: V
+/// \theta(n * capacity) time, \theta(capacity) memory
+pub fn zero_one_knapsack<V>(v: &[V], w: &[usize], capacity: usize) -> V
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    assert_eq!(v.len(), w.len());
+
+    let predecessor = |i: usize, j: usize| j.checked_sub(w[i]);
+    rolling_zero_one(v, capacity + 1, predecessor, false).0[capacity]
+}
 
-    let mut k = vec![vec![0; capacity + 1]; n + 1];
+/// 0/1 knapsack returning the optimal value together with the indices of the chosen objects,
+/// in increasing order. One bit per object and capacity is kept to walk the choices back.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: (V, Vec<usize>)
+/// \theta(n * capacity)
+pub fn zero_one_knapsack_with_items<V>(v: &[V], w: &[usize], capacity: usize) -> (V, Vec<usize>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    assert_eq!(v.len(), w.len());
 
-    for i in 1..=n {
-        for j in 1..=capacity {
-            // Object i fits having capacity j
-            if j >= w[i - 1] {
-                k[i][j] = (k[i - 1][j - w[i - 1]] + v[i - 1]).max(k[i - 1][j]);
-            } else {
-                k[i][j] = k[i - 1][j];
+    let predecessor = |i: usize, j: usize| j.checked_sub(w[i]);
+    let (best, taken) = rolling_zero_one(v, capacity + 1, predecessor, true);
+    let items = reconstruct(&taken, capacity, predecessor);
+    (best[capacity], items)
+}
+
+/// Unbounded knapsack: every object can be taken any number of times.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects, all positive
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: V
+/// \theta(n * capacity) time, \theta(capacity) memory
+pub fn unbounded_knapsack<V>(v: &[V], w: &[usize], capacity: usize) -> V
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    unbounded_knapsack_with_items(v, w, capacity).0
+}
+
+/// Unbounded knapsack returning the optimal value together with how many copies of each object
+/// are taken. Storing the last object added for every capacity is enough to rebuild the choice.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects, all positive
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: (V, Vec<usize>)
+/// \theta(n * capacity) time, \theta(capacity) memory
+pub fn unbounded_knapsack_with_items<V>(v: &[V], w: &[usize], capacity: usize) -> (V, Vec<usize>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    assert_eq!(v.len(), w.len());
+    assert!(
+        w.iter().all(|&weight| weight > 0),
+        "Unbounded Knapsack: objects must have positive weight"
+    );
+
+    // best[j] is the maximum value with weight at most j, last[j] the last object added
+    let mut best = vec![V::default(); capacity + 1];
+    let mut last = vec![None; capacity + 1];
+    for j in 1..=capacity {
+        for i in 0..v.len() {
+            if w[i] <= j && best[j - w[i]] + v[i] > best[j] {
+                best[j] = best[j - w[i]] + v[i];
+                last[j] = Some(i);
             }
         }
     }
 
-    k[n][capacity]
+    let mut copies = vec![0; v.len()];
+    let mut j = capacity;
+    while let Some(i) = last[j] {
+        copies[i] += 1;
+        j -= w[i];
+    }
+
+    (best[capacity], copies)
 }
 
-pub fn fractional_knapsack(v: Vec<i32>, w: Vec<usize>, capacity: usize) -> f32 {
-    let n = v.len();
-    assert_eq!(n, w.len());
+/// Bounded knapsack: object i can be taken up to c[i] times.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects
+/// * `c`: the number of available copies of each object
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: V
+/// \theta(capacity * \sum \log c[i]) time, \theta(capacity) memory
+pub fn bounded_knapsack<V>(v: &[V], w: &[usize], c: &[usize], capacity: usize) -> V
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    let (values, weights, _) = binary_splitting(v, w, c);
+    zero_one_knapsack(&values, &weights, capacity)
+}
 
-    let mut objects = (0..n).collect::<Vec<_>>();
-    objects.sort_by_key(|&i| v[i] / w[i] as i32);
+/// Bounded knapsack returning the optimal value together with how many copies of each object
+/// are taken.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects
+/// * `c`: the number of available copies of each object
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: (V, Vec<usize>)
+/// \theta(capacity * \sum \log c[i])
+pub fn bounded_knapsack_with_items<V>(
+    v: &[V],
+    w: &[usize],
+    c: &[usize],
+    capacity: usize,
+) -> (V, Vec<usize>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    let (values, weights, pieces) = binary_splitting(v, w, c);
+    let (best, chosen) = zero_one_knapsack_with_items(&values, &weights, capacity);
 
-    let mut res = 0.;
-    let mut left_capacity = capacity;
-    let mut i = 0;
-    while i < n {
-        if w[i] <= left_capacity {
-            res = res + v[i] as f32;
-        } else {
-            res = res + (left_capacity / w[i]) as f32 * v[i] as f32;
-        }
-        i = i + 1;
-        left_capacity = left_capacity - w[i];
+    let mut copies = vec![0; v.len()];
+    for piece in chosen {
+        let (i, k) = pieces[piece];
+        copies[i] += k;
     }
 
-    res
+    (best, copies)
 }
 
-pub fn subset_sum(s: Vec<i32>, v: usize) -> bool {
-    let n = s.len();
-    let mut t = vec![vec![false; v + 1]; n + 1];
+/// Splits c copies of an object into pieces of 1, 2, 4, ... copies and a remainder, so that
+/// every number of copies up to c is the sum of a subset of the pieces. Returns the values and
+/// weights of the pieces, and for every piece its object and number of copies.
+fn binary_splitting<V>(
+    v: &[V],
+    w: &[usize],
+    c: &[usize],
+) -> (Vec<V>, Vec<usize>, Vec<(usize, usize)>)
+where
+    V: Copy + Default + Add<Output = V>,
+{
+    assert_eq!(v.len(), w.len());
+    assert_eq!(v.len(), c.len());
 
-    for i in 0..=n {
-        t[i][0] = true;
+    let mut pieces = Vec::new();
+    for (i, &copies) in c.iter().enumerate() {
+        let mut left = copies;
+        let mut k = 1;
+        while left > 0 {
+            let take = k.min(left);
+            pieces.push((i, take));
+            left -= take;
+            k *= 2;
+        }
     }
 
-    for i in 1..=n {
-        for j in 1..=v {
-            if s[i - 1] <= j as i32 {
-                t[i][j] = t[i - 1][j - s[i - 1] as usize] || t[i - 1][j];
-            } else {
-                t[i][j] = t[i - 1][j];
+    let values = pieces.iter().map(|&(i, k)| times(v[i], k)).collect();
+    let weights = pieces.iter().map(|&(i, k)| w[i] * k).collect();
+    (values, weights, pieces)
+}
+
+/// Returns k * x using only additions, by doubling.
+fn times<V>(x: V, k: usize) -> V
+where
+    V: Copy + Default + Add<Output = V>,
+{
+    match k {
+        0 => V::default(),
+        _ => {
+            let half = times(x, k / 2);
+            match k % 2 {
+                0 => half + half,
+                _ => half + half + x,
             }
         }
     }
+}
 
-    t[n][v]
+/// Multi-dimensional 0/1 knapsack: every object has a weight for each dimension and the total
+/// weight must fit the capacity in every dimension.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects, one per dimension
+/// * `capacities`: the capacity of the knapsack in each dimension
+///
+/// returns: V
+/// \theta(n * d * \prod (capacities[k] + 1)) time, \theta(\prod (capacities[k] + 1)) memory
+pub fn multidimensional_knapsack<V>(v: &[V], w: &[Vec<usize>], capacities: &[usize]) -> V
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    multidimensional_knapsack_impl(v, w, capacities, false).0
 }
 
-pub fn coin_change(c: Vec<usize>, k: usize) -> usize {
-    let n = c.len();
-    let mut t = vec![vec![0; k + 1]; n + 1];
+/// Multi-dimensional 0/1 knapsack returning the optimal value together with the indices of the
+/// chosen objects, in increasing order.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects, one per dimension
+/// * `capacities`: the capacity of the knapsack in each dimension
+///
+/// returns: (V, Vec<usize>)
+/// \theta(n * d * \prod (capacities[k] + 1))
+pub fn multidimensional_knapsack_with_items<V>(
+    v: &[V],
+    w: &[Vec<usize>],
+    capacities: &[usize],
+) -> (V, Vec<usize>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    multidimensional_knapsack_impl(v, w, capacities, true)
+}
+
+fn multidimensional_knapsack_impl<V>(
+    v: &[V],
+    w: &[Vec<usize>],
+    capacities: &[usize],
+    with_items: bool,
+) -> (V, Vec<usize>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    assert_eq!(v.len(), w.len());
+    assert!(w.iter().all(|weights| weights.len() == capacities.len()));
+
+    // the capacities are flattened in mixed radix, the first dimension being the least
+    // significant one, so a predecessor always has a smaller or equal index
+    let states: usize = capacities.iter().map(|&c| c + 1).product();
+    let predecessor = |i: usize, state: usize| {
+        let mut rest = state;
+        let mut stride = 1;
+        let mut offset = 0;
+        for (&weight, &c) in w[i].iter().zip(capacities) {
+            if rest % (c + 1) < weight {
+                return None;
+            }
+            offset += weight * stride;
+            rest /= c + 1;
+            stride *= c + 1;
+        }
+        Some(state - offset)
+    };
+
+    let (best, taken) = rolling_zero_one(v, states, predecessor, with_items);
+    let items = if with_items {
+        reconstruct(&taken, states - 1, predecessor)
+    } else {
+        Vec::new()
+    };
+    (best[states - 1], items)
+}
+
+/// Grouped (multiple-choice) knapsack: the objects are split in groups, and at most one choice
+/// (weight, value) can be taken from each group. Returns the optimal value together with the
+/// index of the choice taken in every group, None if the group is skipped. A single row is
+/// updated per group from the largest capacity, reading only smaller or equal capacities not
+/// yet updated, and the choices are kept to walk them back.
+///
+/// # Arguments
+///
+/// * `groups`: the choices (weight, value) of every group
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: (V, Vec<Option<usize>>)
+/// \theta(capacity * \sum |groups[g]|) time, \theta(capacity * groups) memory
+pub fn grouped_knapsack<V>(groups: &[Vec<(usize, V)>], capacity: usize) -> (V, Vec<Option<usize>>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+{
+    // best[j] is the maximum value with weight at most j
+    let mut best = vec![V::default(); capacity + 1];
+    let mut chosen = Vec::with_capacity(groups.len());
+
+    for choices in groups {
+        let mut row = vec![None; capacity + 1];
+        for j in (0..=capacity).rev() {
+            let mut improved = best[j];
+            for (c, &(weight, value)) in choices.iter().enumerate() {
+                if weight <= j && best[j - weight] + value > improved {
+                    improved = best[j - weight] + value;
+                    row[j] = Some(c);
+                }
+            }
+            best[j] = improved;
+        }
+        chosen.push(row);
+    }
 
-    for i in 0..=n {
-        t[i][0] = 1;
+    let mut choices = vec![None; groups.len()];
+    let mut j = capacity;
+    for g in (0..groups.len()).rev() {
+        if let Some(c) = chosen[g][j] {
+            choices[g] = Some(c);
+            j -= groups[g][c].0;
+        }
     }
+    (best[capacity], choices)
+}
+
+/// Runs the 0/1 knapsack recurrence on a single row of states, visited from the largest one.
+/// `predecessor(i, s)` is the state left after removing object i from the state s, if it fits,
+/// and must not be larger than s. If asked, keeps for every object and state whether taking
+/// the object improved the state.
+fn rolling_zero_one<V, P>(
+    v: &[V],
+    states: usize,
+    predecessor: P,
+    with_items: bool,
+) -> (Vec<V>, Vec<Vec<bool>>)
+where
+    V: Copy + Default + Ord + Add<Output = V>,
+    P: Fn(usize, usize) -> Option<usize>,
+{
+    let mut best = vec![V::default(); states];
+    let mut taken = Vec::new();
 
-    for i in 1..=n {
-        for j in 1..=k {
-            if c[i - 1] <= j {
-                t[i][j] = t[i - 1][j] + t[i][j - c[i - 1]];
-            } else {
-                t[i][j] = t[i - 1][j];
+    for (i, &value) in v.iter().enumerate() {
+        let mut row = vec![false; if with_items { states } else { 0 }];
+        for s in (0..states).rev() {
+            if let Some(p) = predecessor(i, s) {
+                if best[p] + value > best[s] {
+                    best[s] = best[p] + value;
+                    if with_items {
+                        row[s] = true;
+                    }
+                }
             }
         }
+        if with_items {
+            taken.push(row);
+        }
+    }
+
+    (best, taken)
+}
+
+/// Walks the choices of `rolling_zero_one` back from the given state.
+fn reconstruct<P>(taken: &[Vec<bool>], mut state: usize, predecessor: P) -> Vec<usize>
+where
+    P: Fn(usize, usize) -> Option<usize>,
+{
+    let mut items = Vec::new();
+    for i in (0..taken.len()).rev() {
+        if taken[i][state] {
+            items.push(i);
+            state = predecessor(i, state).unwrap();
+        }
+    }
+    items.reverse();
+    items
+}
+
+/// Fractional knapsack: objects can be split, so they are taken greedily by decreasing value
+/// per unit of weight. Returns the optimal value and the fraction taken of each object.
+///
+/// # Arguments
+///
+/// * `v`: the values of the objects
+/// * `w`: the weights of the objects
+/// * `capacity`: the capacity of the knapsack
+///
+/// returns: (f64, Vec<f64>)
+/// \theta(n \log n)
+pub fn fractional_knapsack(v: Vec<i32>, w: Vec<usize>, capacity: usize) -> (f64, Vec<f64>) {
+    let n = v.len();
+    assert_eq!(n, w.len());
+
+    // compare v[i] / w[i] and v[j] / w[j] as v[i] * w[j] and v[j] * w[i], without rounding
+    let mut objects = (0..n).filter(|&i| v[i] > 0).collect::<Vec<_>>();
+    objects.sort_by(|&i, &j| {
+        let a = v[i] as i128 * w[j] as i128;
+        let b = v[j] as i128 * w[i] as i128;
+        match a.cmp(&b) {
+            Ordering::Equal => i.cmp(&j),
+            ordering => ordering.reverse(),
+        }
+    });
+
+    let mut value = 0.;
+    let mut fractions = vec![0.; n];
+    let mut left_capacity = capacity;
+    for i in objects {
+        if w[i] <= left_capacity {
+            fractions[i] = 1.;
+            left_capacity -= w[i];
+        } else {
+            fractions[i] = left_capacity as f64 / w[i] as f64;
+            left_capacity = 0;
+        }
+        value += fractions[i] * v[i] as f64;
     }
 
-    t[n][k]
+    (value, fractions)
 }
 
 #[test]
@@ -92,25 +453,169 @@ pub fn test_knapsack() {
 }
 
 #[test]
-pub fn test_fractional_knapsack() {
-    let v = vec![60, 100, 120];
-    let w = vec![10, 20, 30];
-    let capacity = 50;
-    assert_eq!(fractional_knapsack(v, w, capacity), 240.);
+pub fn test_zero_one_knapsack_with_items() {
+    let v = [60, 100, 120];
+    let w = [10, 20, 30];
+    assert_eq!(zero_one_knapsack_with_items(&v, &w, 50), (220, vec![1, 2]));
+    assert_eq!(zero_one_knapsack_with_items(&v, &w, 5), (0, vec![]));
+
+    // brute force over all the subsets
+    let v: Vec<u64> = (0..12).map(|i| (i * 37 % 23) as u64 + 1).collect();
+    let w: Vec<usize> = (0..12).map(|i| i * 11 % 9).collect();
+    for capacity in 0..40 {
+        let mut expected = 0;
+        for mask in 0..1_usize << v.len() {
+            let items = (0..v.len()).filter(|&i| mask >> i & 1 == 1);
+            if items.clone().map(|i| w[i]).sum::<usize>() <= capacity {
+                expected = expected.max(items.map(|i| v[i]).sum());
+            }
+        }
+
+        let (best, items) = zero_one_knapsack_with_items(&v, &w, capacity);
+        assert_eq!(best, expected);
+        assert_eq!(zero_one_knapsack(&v, &w, capacity), expected);
+        assert_eq!(items.iter().map(|&i| v[i]).sum::<u64>(), best);
+        assert!(items.iter().map(|&i| w[i]).sum::<usize>() <= capacity);
+    }
 }
 
 #[test]
-pub fn test_subset_sum() {
-    let s = vec![3, 34, 4, 12, 5, 2];
-    assert_eq!(subset_sum(s.clone(), 9), true);
-    assert_eq!(subset_sum(s.clone(), 30), false);
-    assert_eq!(subset_sum(s.clone(), 14), true);
+pub fn test_unbounded_knapsack() {
+    let v = [10, 40, 50, 70];
+    let w = [1, 3, 4, 5];
+    assert_eq!(unbounded_knapsack(&v, &w, 8), 110);
+
+    let (best, copies) = unbounded_knapsack_with_items(&v, &w, 8);
+    assert_eq!(best, 110);
+    assert_eq!((0..4).map(|i| copies[i] * w[i]).sum::<usize>(), 8);
+    assert_eq!((0..4).map(|i| copies[i] as i32 * v[i]).sum::<i32>(), 110);
 }
 
 #[test]
-pub fn test_coin_change() {
-    let c = vec![1, 2, 3];
-    assert_eq!(coin_change(c, 4), 4);
-    let c = vec![2, 5, 3, 6];
-    assert_eq!(coin_change(c, 10), 5);
+pub fn test_bounded_knapsack() {
+    let v = [4, 7, 3, 9];
+    let w = [2, 3, 1, 5];
+    let c = [3, 1, 5, 2];
+
+    // the same objects repeated c[i] times in a 0/1 knapsack
+    let (mut expanded_v, mut expanded_w) = (Vec::new(), Vec::new());
+    for i in 0..v.len() {
+        expanded_v.extend(std::iter::repeat_n(v[i], c[i]));
+        expanded_w.extend(std::iter::repeat_n(w[i], c[i]));
+    }
+
+    for capacity in 0..30 {
+        let expected = zero_one_knapsack(&expanded_v, &expanded_w, capacity);
+        assert_eq!(bounded_knapsack(&v, &w, &c, capacity), expected);
+
+        let (best, copies) = bounded_knapsack_with_items(&v, &w, &c, capacity);
+        assert_eq!(best, expected);
+        assert!((0..v.len()).all(|i| copies[i] <= c[i]));
+        assert!((0..v.len()).map(|i| copies[i] * w[i]).sum::<usize>() <= capacity);
+        assert_eq!(
+            (0..v.len()).map(|i| copies[i] as i32 * v[i]).sum::<i32>(),
+            best
+        );
+    }
+}
+
+#[test]
+pub fn test_multidimensional_knapsack() {
+    // (value, weight, volume)
+    let objects = [(10, 5, 1), (40, 4, 8), (30, 6, 2), (50, 3, 6), (25, 2, 4)];
+    let v: Vec<i32> = objects.iter().map(|o| o.0).collect();
+    let w: Vec<Vec<usize>> = objects.iter().map(|o| vec![o.1, o.2]).collect();
+
+    for (weight, volume) in [(10, 10), (15, 12), (0, 5), (20, 20)] {
+        let mut expected = 0;
+        for mask in 0..1_usize << objects.len() {
+            let chosen: Vec<_> = (0..objects.len()).filter(|&i| mask >> i & 1 == 1).collect();
+            if chosen.iter().map(|&i| objects[i].1).sum::<usize>() <= weight
+                && chosen.iter().map(|&i| objects[i].2).sum::<usize>() <= volume
+            {
+                expected = expected.max(chosen.iter().map(|&i| objects[i].0).sum());
+            }
+        }
+
+        assert_eq!(
+            multidimensional_knapsack(&v, &w, &[weight, volume]),
+            expected
+        );
+        let (best, items) = multidimensional_knapsack_with_items(&v, &w, &[weight, volume]);
+        assert_eq!(best, expected);
+        assert_eq!(items.iter().map(|&i| v[i]).sum::<i32>(), best);
+    }
+}
+
+#[test]
+pub fn test_grouped_knapsack() {
+    let groups = vec![
+        vec![(1, 5), (2, 8), (3, 9)],
+        vec![(2, 7), (4, 15)],
+        vec![(1, 2), (3, 10)],
+    ];
+    assert_eq!(
+        grouped_knapsack(&groups, 5),
+        (20, vec![Some(0), Some(1), None])
+    );
+    assert_eq!(grouped_knapsack(&groups, 0), (0, vec![None, None, None]));
+    assert_eq!(grouped_knapsack::<i64>(&[], 10), (0, vec![]));
+
+    // brute force over every choice in every group, negative values and zero weights included
+    let mut rng = Lcg::new(19);
+    let mut random = |modulus: u64| rng.below(modulus);
+    for _ in 0..30 {
+        let groups: Vec<Vec<(usize, i64)>> = (0..1 + random(4))
+            .map(|_| {
+                (0..random(4))
+                    .map(|_| (random(6) as usize, random(30) as i64 - 8))
+                    .collect()
+            })
+            .collect();
+        let capacity = random(12) as usize;
+
+        let mut best = 0;
+        let mut selection = vec![0; groups.len()];
+        loop {
+            let picked = groups
+                .iter()
+                .zip(selection.iter())
+                .filter(|&(_, &s)| s > 0)
+                .map(|(g, &s)| g[s - 1]);
+            let weight: usize = picked.clone().map(|(w, _)| w).sum();
+            if weight <= capacity {
+                best = best.max(picked.map(|(_, v)| v).sum());
+            }
+            // next selection, in mixed radix
+            let Some(g) = (0..groups.len()).find(|&g| selection[g] < groups[g].len()) else {
+                break;
+            };
+            selection[g] += 1;
+            selection[..g].iter_mut().for_each(|s| *s = 0);
+        }
+
+        let (value, choices) = grouped_knapsack(&groups, capacity);
+        assert_eq!(value, best);
+        let picked = choices
+            .iter()
+            .enumerate()
+            .filter_map(|(g, c)| c.map(|c| groups[g][c]));
+        assert!(picked.clone().map(|(w, _)| w).sum::<usize>() <= capacity);
+        assert_eq!(picked.map(|(_, v)| v).sum::<i64>(), best);
+    }
+}
+
+#[test]
+pub fn test_fractional_knapsack() {
+    let v = vec![60, 100, 120];
+    let w = vec![10, 20, 30];
+    let capacity = 50;
+    let (value, fractions) = fractional_knapsack(v, w, capacity);
+    assert!((value - 240.).abs() < 1e-9);
+    assert_eq!(fractions[..2], [1., 1.]);
+    assert!((fractions[2] - 2. / 3.).abs() < 1e-9);
+
+    let (value, fractions) = fractional_knapsack(vec![5, 3, 8], vec![3, 0, 4], 2);
+    assert!((value - 7.).abs() < 1e-9);
+    assert_eq!(fractions, vec![0., 1., 0.5]);
 }
@@ -0,0 +1,278 @@
+use crate::utilities::matrix::{Matrix, Modular, Semiring};
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+
+// A linear recurrence of order k is a(n) = c[0] * a(n - 1) + ... + c[k - 1] * a(n - k),
+// given by its coefficients c and its first k terms a(0), ..., a(k - 1).
+
+/// Computes the nth term of a linear recurrence modulo p by raising its companion matrix,
+/// which maps (a(i + k - 1), ..., a(i)) to (a(i + k), ..., a(i + 1)), to the power n.
+///
+/// # Arguments
+///
+/// * `coefficients`: the coefficients c of the recurrence
+/// * `initial`: the first k terms
+/// * `n`: the index of the term to find
+/// * `p`: the modulus
+///
+/// returns: u64
+/// \theta(k^3 \log n)
+pub fn nth_term_matrix(coefficients: &[u64], initial: &[u64], n: u64, p: u64) -> u64 {
+    assert_valid(coefficients, initial, p);
+    let k = coefficients.len();
+    if k == 0 {
+        return 0;
+    }
+
+    let modular = Modular { modulus: p };
+    let mut companion = Matrix::from_rows(vec![vec![0; k]; k]);
+    for (j, &c) in coefficients.iter().enumerate() {
+        companion.set(0, j, c % p);
+    }
+    for i in 1..k {
+        companion.set(i, i - 1, 1 % p);
+    }
+    let state = Matrix::from_rows(initial.iter().rev().map(|&a| vec![a % p]).collect());
+
+    *companion
+        .pow(n, &modular)
+        .mul(&state, &modular)
+        .get(k - 1, 0)
+}
+
+/// Computes the nth term of a linear recurrence modulo p with Kitamasa's method: x^n modulo
+/// the characteristic polynomial x^k - c[0] * x^(k - 1) - ... - c[k - 1] is a combination
+/// r[0] + r[1] * x + ... of degree less than k, and a(n) = r[0] * a(0) + r[1] * a(1) + ...
+///
+/// # Arguments
+///
+/// * `coefficients`: the coefficients c of the recurrence
+/// * `initial`: the first k terms
+/// * `n`: the index of the term to find
+/// * `p`: the modulus
+///
+/// returns: u64
+/// \theta(k^2 \log n)
+pub fn nth_term_kitamasa(coefficients: &[u64], initial: &[u64], n: u64, p: u64) -> u64 {
+    assert_valid(coefficients, initial, p);
+    let k = coefficients.len();
+    if k == 0 {
+        return 0;
+    }
+
+    let modular = Modular { modulus: p };
+    let c: Vec<u64> = coefficients.iter().map(|&c| c % p).collect();
+
+    // product of two polynomials of degree less than k, reduced with x^k = c[0] * x^(k - 1) + ...
+    let mul_mod = |a: &[u64], b: &[u64]| -> Vec<u64> {
+        let mut product = vec![0; 2 * k - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                product[i + j] = modular.add(&product[i + j], &modular.mul(x, y));
+            }
+        }
+        for d in (k..2 * k - 1).rev() {
+            let top = product[d];
+            for (i, ci) in c.iter().enumerate() {
+                product[d - 1 - i] = modular.add(&product[d - 1 - i], &modular.mul(&top, ci));
+            }
+        }
+        product.truncate(k);
+        product
+    };
+
+    let mut result = vec![0; k];
+    result[0] = 1 % p;
+    // x modulo the characteristic polynomial
+    let mut base = vec![0; k];
+    match k {
+        1 => base[0] = c[0],
+        _ => base[1] = 1 % p,
+    }
+
+    let mut e = n;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(&result, &base);
+        }
+        e >>= 1;
+        if e > 0 {
+            base = mul_mod(&base, &base);
+        }
+    }
+
+    result.iter().zip(initial).fold(0, |sum, (r, a)| {
+        modular.add(&sum, &modular.mul(r, &(a % p)))
+    })
+}
+
+/// Finds the shortest linear recurrence generating the given sequence modulo a prime p, with
+/// the Berlekamp-Massey algorithm. 2k terms are enough to recover a recurrence of order k.
+///
+/// # Arguments
+///
+/// * `sequence`: the first terms of the sequence
+/// * `p`: the modulus, a prime
+///
+/// returns: Vec<u64>, the coefficients c of the recurrence
+/// \theta(n^2)
+pub fn berlekamp_massey(sequence: &[u64], p: u64) -> Vec<u64> {
+    assert!(p > 1, "Berlekamp-Massey: the modulus must be a prime");
+    let modular = Modular { modulus: p };
+    let s: Vec<u64> = sequence.iter().map(|&a| a % p).collect();
+
+    // connection polynomials 1 + d[1] * x + ... with s(n) + d[1] * s(n - 1) + ... = 0: the
+    // current one, and the one before the last change of length, with its discrepancy
+    let mut current = vec![1 % p];
+    let mut previous = vec![1 % p];
+    let mut length = 0;
+    let mut previous_discrepancy = 1;
+    let mut shift = 1;
+
+    for n in 0..s.len() {
+        let discrepancy = (1..=length).fold(s[n], |d, i| {
+            modular.add(&d, &modular.mul(&current[i], &s[n - i]))
+        });
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let factor = modular.mul(&discrepancy, &inverse(previous_discrepancy, p));
+        let before = current.clone();
+        if current.len() < previous.len() + shift {
+            current.resize(previous.len() + shift, 0);
+        }
+        for (i, b) in previous.iter().enumerate() {
+            let term = modular.mul(&factor, b);
+            current[i + shift] = modular.add(&current[i + shift], &(p - term));
+        }
+
+        if 2 * length <= n {
+            length = n + 1 - length;
+            previous = before;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+    }
+
+    current.resize(length + 1, 0);
+    current[1..].iter().map(|&d| (p - d) % p).collect()
+}
+
+/// Computes the nth term of a sequence modulo a prime p from its first terms, recovering the
+/// recurrence with Berlekamp-Massey and evaluating it with Kitamasa's method.
+///
+/// # Arguments
+///
+/// * `sequence`: the first terms, at least twice the order of the recurrence
+/// * `n`: the index of the term to find
+/// * `p`: the modulus, a prime
+///
+/// returns: u64
+/// \theta(m^2 + k^2 \log n), m being the number of given terms
+pub fn nth_term_from_sequence(sequence: &[u64], n: u64, p: u64) -> u64 {
+    if let Some(&a) = usize::try_from(n).ok().and_then(|i| sequence.get(i)) {
+        return a % p;
+    }
+    let coefficients = berlekamp_massey(sequence, p);
+    let k = coefficients.len();
+    nth_term_kitamasa(&coefficients, &sequence[..k], n, p)
+}
+
+fn assert_valid(coefficients: &[u64], initial: &[u64], p: u64) {
+    assert_eq!(
+        coefficients.len(),
+        initial.len(),
+        "Linear Recurrence: k coefficients need k initial terms"
+    );
+    assert!(p > 0, "Linear Recurrence: the modulus must be positive");
+}
+
+/// Modular inverse by Fermat's little theorem, p being prime.
+fn inverse(a: u64, p: u64) -> u64 {
+    let modular = Modular { modulus: p };
+    let mut result = 1;
+    let mut base = a % p;
+    let mut e = p - 2;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = modular.mul(&result, &base);
+        }
+        base = modular.mul(&base, &base);
+        e >>= 1;
+    }
+    result
+}
+
+/// Computes the first n terms of the recurrence one by one.
+#[cfg(test)]
+fn naive_terms(coefficients: &[u64], initial: &[u64], n: usize, p: u64) -> Vec<u64> {
+    let k = coefficients.len();
+    let mut terms: Vec<u64> = initial.iter().map(|&a| a % p).collect();
+    while terms.len() < n {
+        let i = terms.len();
+        let next = (0..k).fold(0, |sum, j| {
+            (sum + coefficients[j] % p * terms[i - 1 - j]) % p
+        });
+        terms.push(next);
+    }
+    terms.truncate(n);
+    terms
+}
+
+#[test]
+pub fn test_nth_term() {
+    let p = 1_000_000_007;
+    // fibonacci, tribonacci and a(n) = 2 a(n - 1) + 3 a(n - 2) - a(n - 3)
+    let cases: [(&[u64], &[u64]); 4] = [
+        (&[1, 1], &[0, 1]),
+        (&[1, 1, 1], &[0, 0, 1]),
+        (&[2, 3, p - 1], &[5, 1, 4]),
+        (&[7], &[3]),
+    ];
+    for (coefficients, initial) in cases {
+        let terms = naive_terms(coefficients, initial, 60, p);
+        for (n, &a) in terms.iter().enumerate() {
+            assert_eq!(nth_term_matrix(coefficients, initial, n as u64, p), a);
+            assert_eq!(nth_term_kitamasa(coefficients, initial, n as u64, p), a);
+        }
+    }
+
+    assert_eq!(
+        nth_term_matrix(&[1, 1], &[0, 1], 1_000_000_000_000_000_000, p),
+        209_783_453
+    );
+    assert_eq!(
+        nth_term_kitamasa(&[1, 1], &[0, 1], 1_000_000_000_000_000_000, p),
+        209_783_453
+    );
+    assert_eq!(nth_term_matrix(&[1, 1], &[0, 1], 100, 1), 0);
+}
+
+#[test]
+pub fn test_berlekamp_massey() {
+    let p = 998_244_353;
+    let mut rng = Lcg::new(42);
+    for k in 1..6 {
+        let mut random = || rng.below(p);
+        let coefficients: Vec<u64> = (0..k).map(|_| random()).collect();
+        let initial: Vec<u64> = (0..k).map(|_| random()).collect();
+        let terms = naive_terms(&coefficients, &initial, 2 * k + 10, p);
+
+        let found = berlekamp_massey(&terms[..2 * k], p);
+        assert!(found.len() <= k);
+        let rebuilt = naive_terms(&found, &terms[..found.len()], terms.len(), p);
+        assert_eq!(rebuilt, terms);
+
+        let far = nth_term_kitamasa(&coefficients, &initial, 1 << 40, p);
+        assert_eq!(nth_term_from_sequence(&terms[..2 * k], 1 << 40, p), far);
+    }
+
+    assert_eq!(berlekamp_massey(&[1, 1, 2, 3, 5, 8, 13], p), vec![1, 1]);
+    assert_eq!(berlekamp_massey(&[0, 0, 0], p), vec![]);
+    assert_eq!(berlekamp_massey(&[1, 2, 4, 8, 16], p), vec![2]);
+    assert_eq!(nth_term_from_sequence(&[1, 2, 4, 8], 2, p), 4);
+}
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Where the memoized values are kept.
+pub trait Storage<K, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn insert(&mut self, key: K, value: V);
+}
+
+impl<K, V> Storage<K, V> for HashMap<K, V>
+where
+    K: Eq + Hash,
+{
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+}
+
+/// Preallocated storage for states indexed by 0..size, such as i * m + j for a table.
+pub struct DenseStorage<V> {
+    values: Vec<Option<V>>,
+}
+
+impl<V: Clone> DenseStorage<V> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            values: vec![None; size],
+        }
+    }
+}
+
+impl<V> Storage<usize, V> for DenseStorage<V> {
+    fn get(&self, key: &usize) -> Option<&V> {
+        self.values[*key].as_ref()
+    }
+
+    fn insert(&mut self, key: usize, value: V) {
+        self.values[key] = Some(value);
+    }
+}
+
+/// A recurrence computing the value of a state, calling `get` on the memo for the states it
+/// depends on.
+pub type Recurrence<'f, K, V, S> = dyn Fn(&mut Memo<'f, K, V, S>, K) -> V + 'f;
+
+/// A recursive function memoized in a storage, every state being computed at most once. The
+/// recursion uses the call stack, see `memoize_iterative` for deep dependency chains.
+pub struct Memo<'f, K, V, S> {
+    storage: S,
+    recurrence: &'f Recurrence<'f, K, V, S>,
+}
+
+impl<'f, K, V, S> Memo<'f, K, V, S>
+where
+    K: Clone,
+    V: Clone,
+    S: Storage<K, V>,
+{
+    pub fn new(storage: S, recurrence: &'f Recurrence<'f, K, V, S>) -> Self {
+        Self {
+            storage,
+            recurrence,
+        }
+    }
+
+    /// Returns the value of the state, computing it with the recurrence the first time.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: the state
+    ///
+    /// returns: V
+    /// \theta(1) storage operations besides the first computation
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.storage.get(&key) {
+            return value.clone();
+        }
+        let recurrence = self.recurrence;
+        let value = recurrence(self, key.clone());
+        self.storage.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the storage, with the values of all the states computed so far.
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+}
+
+/// Computes the value of a state whose recurrence is given as its list of dependencies and a
+/// function combining their values, visiting the dependency graph with an explicit stack
+/// instead of recursion. Panics if the dependencies are cyclic.
+///
+/// # Arguments
+///
+/// * `storage`: the storage of the values, possibly holding some already
+/// * `root`: the state to compute
+/// * `dependencies`: the states the value of a state depends on
+/// * `combine`: the value of a state given the values of its dependencies, in the same order
+///
+/// returns: V
+/// \theta(states + dependencies) storage operations
+pub fn memoize_iterative<K, V, S, D, C>(storage: &mut S, root: K, dependencies: D, combine: C) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    S: Storage<K, V>,
+    D: Fn(&K) -> Vec<K>,
+    C: Fn(&K, &[V]) -> V,
+{
+    // (state, whether its dependencies have already been pushed)
+    let mut stack = vec![(root.clone(), false)];
+    // the states expanded but not computed yet: every state above them on the stack is one of
+    // their dependencies, so meeting one of them again closes a cycle
+    let mut in_progress = HashSet::new();
+    while let Some((key, expanded)) = stack.pop() {
+        if storage.get(&key).is_some() {
+            continue;
+        }
+
+        let needed = dependencies(&key);
+        match expanded {
+            false => {
+                assert!(
+                    in_progress.insert(key.clone()),
+                    "Memoization: cyclic dependencies"
+                );
+                stack.push((key, true));
+                for dependency in needed {
+                    if storage.get(&dependency).is_none() {
+                        stack.push((dependency, false));
+                    }
+                }
+            }
+            true => {
+                let values: Vec<V> = needed
+                    .iter()
+                    .map(|dependency| {
+                        storage
+                            .get(dependency)
+                            .expect("Memoization: the dependencies of a state changed")
+                            .clone()
+                    })
+                    .collect();
+                in_progress.remove(&key);
+                let value = combine(&key, &values);
+                storage.insert(key, value);
+            }
+        }
+    }
+    storage.get(&root).unwrap().clone()
+}
+
+#[test]
+pub fn test_memo() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let fibonacci = |memo: &mut Memo<u64, u64, HashMap<u64, u64>>, n: u64| {
+        calls.set(calls.get() + 1);
+        match n {
+            0 | 1 => n,
+            _ => memo.get(n - 1) + memo.get(n - 2),
+        }
+    };
+    let mut memo = Memo::new(HashMap::new(), &fibonacci);
+    assert_eq!(memo.get(90), 2_880_067_194_370_816_120);
+    assert_eq!(memo.get(50), 12_586_269_025);
+    assert_eq!(calls.get(), 91);
+    assert_eq!(memo.into_storage().len(), 91);
+
+    // binomial coefficients on a dense table indexed by n * 31 + k
+    let binomial = |memo: &mut Memo<usize, u64, DenseStorage<u64>>, key: usize| {
+        let (n, k) = (key / 31, key % 31);
+        match k == 0 || k == n {
+            true => 1,
+            false => memo.get((n - 1) * 31 + k - 1) + memo.get((n - 1) * 31 + k),
+        }
+    };
+    let mut memo = Memo::new(DenseStorage::new(31 * 31), &binomial);
+    assert_eq!(memo.get(30 * 31 + 15), 155_117_520);
+    assert_eq!(memo.get(10 * 31 + 3), 120);
+
+    // sparse states: the length of the Collatz sequences
+    let collatz = |memo: &mut Memo<u64, u32, HashMap<u64, u32>>, n: u64| match n {
+        1 => 0,
+        _ if n.is_multiple_of(2) => memo.get(n / 2) + 1,
+        _ => memo.get(3 * n + 1) + 1,
+    };
+    let mut memo = Memo::new(HashMap::new(), &collatz);
+    assert_eq!(memo.get(27), 111);
+    assert_eq!((1..10).map(|n| memo.get(n)).max(), Some(19));
+}
+
+#[test]
+pub fn test_memoize_iterative() {
+    // a chain of a million dependencies, too deep for the call stack
+    let n = 1_000_000;
+    let p = 1_000_000_007;
+    let mut storage = DenseStorage::new(n + 1);
+    let value = memoize_iterative(
+        &mut storage,
+        n,
+        |&i| match i {
+            0 | 1 => vec![],
+            _ => vec![i - 1, i - 2],
+        },
+        |&i, values: &[u64]| match i {
+            0 | 1 => i as u64,
+            _ => (values[0] + values[1]) % p,
+        },
+    );
+    assert_eq!(
+        value,
+        crate::dynamic_programming::easy_problems::fibonacci_mod(n as u64, p)
+    );
+
+    // the same recurrence as the recursive version, with a shared storage
+    let mut storage: HashMap<(usize, usize), u64> = HashMap::new();
+    let binomial = |storage: &mut HashMap<(usize, usize), u64>, n: usize, k: usize| {
+        memoize_iterative(
+            storage,
+            (n, k),
+            |&(n, k)| match k == 0 || k == n {
+                true => vec![],
+                false => vec![(n - 1, k - 1), (n - 1, k)],
+            },
+            |_, values: &[u64]| values.iter().sum::<u64>().max(1),
+        )
+    };
+    assert_eq!(binomial(&mut storage, 30, 15), 155_117_520);
+    let computed = storage.len();
+    assert_eq!(binomial(&mut storage, 20, 10), 184_756);
+    assert_eq!(storage.len(), computed);
+}
+
+#[test]
+#[should_panic(expected = "Memoization: cyclic dependencies")]
+pub fn test_memoize_iterative_cycle() {
+    // 0 -> 1 -> 2 -> 3 -> 1
+    let mut storage: HashMap<usize, u64> = HashMap::new();
+    memoize_iterative(
+        &mut storage,
+        0,
+        |&i| vec![if i == 3 { 1 } else { i + 1 }],
+        |_, values: &[u64]| values[0],
+    );
+}
@@ -1,3 +1,13 @@
+mod bitmask;
+mod coin_change;
+mod digit_dp;
 mod easy_problems;
-mod knapsack;
-mod subsequences;
+mod grid_paths;
+mod interval_dp;
+pub mod knapsack;
+mod linear_recurrence;
+mod memoization;
+mod optimizations;
+mod sequence_alignment;
+pub mod subsequences;
+mod subset_sum;
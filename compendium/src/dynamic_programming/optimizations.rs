@@ -0,0 +1,563 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::collections::VecDeque;
+
+/// The line y = slope * x + intercept.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub slope: i64,
+    pub intercept: i64,
+}
+
+impl Line {
+    pub fn new(slope: i64, intercept: i64) -> Self {
+        Self { slope, intercept }
+    }
+
+    pub fn eval(&self, x: i64) -> i64 {
+        self.slope * x + self.intercept
+    }
+}
+
+/// Convex hull trick for the minimum of lines inserted by non-increasing slope. The lines
+/// attaining the minimum somewhere form the lower envelope, kept in a deque in order of
+/// decreasing slope, i.e. of the x where they are optimal. For maxima insert the negated
+/// lines and negate the results.
+pub struct MonotoneCht {
+    lines: VecDeque<Line>,
+}
+
+impl MonotoneCht {
+    pub fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Returns whether b is useless between a and c, i.e. c overtakes b no later than b
+    /// overtakes a, with slopes a > b > c.
+    fn is_useless(a: &Line, b: &Line, c: &Line) -> bool {
+        let (a_slope, a_intercept) = (a.slope as i128, a.intercept as i128);
+        let (b_slope, b_intercept) = (b.slope as i128, b.intercept as i128);
+        let (c_slope, c_intercept) = (c.slope as i128, c.intercept as i128);
+        (c_intercept - b_intercept) * (a_slope - b_slope)
+            <= (b_intercept - a_intercept) * (b_slope - c_slope)
+    }
+
+    /// Adds a line, with slope not greater than the ones already added.
+    ///
+    /// # Arguments
+    ///
+    /// * `line`: the line to add
+    ///
+    /// returns: ()
+    /// amortized \theta(1)
+    pub fn add_line(&mut self, line: Line) {
+        if let Some(last) = self.lines.back() {
+            assert!(
+                line.slope <= last.slope,
+                "Convex Hull Trick: slopes must be non-increasing"
+            );
+            if line.slope == last.slope {
+                if line.intercept >= last.intercept {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+        }
+        while self.lines.len() >= 2
+            && Self::is_useless(
+                &self.lines[self.lines.len() - 2],
+                &self.lines[self.lines.len() - 1],
+                &line,
+            )
+        {
+            self.lines.pop_back();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Returns the minimum of the lines at x, the queries having non-decreasing x: the lines
+    /// before the optimal one are never optimal again and are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: the point, not smaller than the previous queries
+    ///
+    /// returns: Option<i64>, None if there are no lines
+    /// amortized \theta(1)
+    pub fn query_monotone(&mut self, x: i64) -> Option<i64> {
+        while self.lines.len() >= 2 && self.lines[1].eval(x) <= self.lines[0].eval(x) {
+            self.lines.pop_front();
+        }
+        self.lines.front().map(|line| line.eval(x))
+    }
+
+    /// Returns the minimum of the lines at any x, with a binary search on the envelope.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: the point
+    ///
+    /// returns: Option<i64>, None if there are no lines
+    /// \theta(\log n)
+    pub fn query(&self, x: i64) -> Option<i64> {
+        // the values at x decrease along the envelope until the optimal line
+        let (mut lo, mut hi) = (0, self.lines.len().saturating_sub(1));
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.lines[mid + 1].eval(x) <= self.lines[mid].eval(x) {
+                true => lo = mid + 1,
+                false => hi = mid,
+            }
+        }
+        self.lines.get(lo).map(|line| line.eval(x))
+    }
+}
+
+impl Default for MonotoneCht {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct LiChaoNode {
+    line: Line,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Li Chao tree for the minimum of lines inserted in any order, on the integer points of
+/// [lo, hi]. Each node of the implicit segment tree keeps the line which is lowest at the
+/// middle of its range; the other line can be lower only on one side, and is pushed there.
+/// The nodes are created along the insertions, so the range can be large.
+pub struct LiChaoTree {
+    lo: i64,
+    hi: i64,
+    nodes: Vec<LiChaoNode>,
+}
+
+impl LiChaoTree {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo <= hi, "Li Chao Tree: empty range");
+        Self {
+            lo,
+            hi,
+            nodes: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds a line.
+    ///
+    /// # Arguments
+    ///
+    /// * `line`: the line to add
+    ///
+    /// returns: ()
+    /// \theta(\log(hi - lo))
+    pub fn add_line(&mut self, mut line: Line) {
+        if self.nodes.is_empty() {
+            self.nodes.push(LiChaoNode {
+                line,
+                left: None,
+                right: None,
+            });
+            return;
+        }
+
+        let (mut node, mut lo, mut hi) = (0, self.lo, self.hi);
+        loop {
+            let mid = lo + (hi - lo) / 2;
+            let current = &mut self.nodes[node].line;
+            if line.eval(mid) < current.eval(mid) {
+                std::mem::swap(&mut line, current);
+            }
+            if lo == hi {
+                return;
+            }
+
+            // the line which is not kept can only be lower on one side of mid
+            let current = self.nodes[node].line;
+            let go_left = line.eval(lo) < current.eval(lo);
+            if !go_left && line.eval(hi) >= current.eval(hi) {
+                return;
+            }
+            let child = match go_left {
+                true => {
+                    hi = mid;
+                    self.nodes[node].left
+                }
+                false => {
+                    lo = mid + 1;
+                    self.nodes[node].right
+                }
+            };
+            node = match child {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(LiChaoNode {
+                        line,
+                        left: None,
+                        right: None,
+                    });
+                    let created = self.nodes.len() - 1;
+                    match go_left {
+                        true => self.nodes[node].left = Some(created),
+                        false => self.nodes[node].right = Some(created),
+                    }
+                    return;
+                }
+            };
+        }
+    }
+
+    /// Returns the minimum of the lines at x, over the nodes on the path to x.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`: the point, in [lo, hi]
+    ///
+    /// returns: Option<i64>, None if there are no lines
+    /// \theta(\log(hi - lo))
+    pub fn query(&self, x: i64) -> Option<i64> {
+        assert!(
+            self.lo <= x && x <= self.hi,
+            "Li Chao Tree: point out of range"
+        );
+
+        let mut result: Option<i64> = None;
+        let (mut node, mut lo, mut hi) = (Some(0).filter(|_| !self.is_empty()), self.lo, self.hi);
+        while let Some(index) = node {
+            let value = self.nodes[index].line.eval(x);
+            result = Some(result.map_or(value, |r| r.min(value)));
+            let mid = lo + (hi - lo) / 2;
+            node = match x <= mid {
+                true => {
+                    hi = mid;
+                    self.nodes[index].left
+                }
+                false => {
+                    lo = mid + 1;
+                    self.nodes[index].right
+                }
+            };
+        }
+        result
+    }
+}
+
+/// Divide and conquer optimization of one layer of a DP: computes
+/// next[i] = min over j of previous[j] + cost(j, i) for i in 0..m, when the smallest optimal
+/// j is non-decreasing in i, which holds when cost satisfies the quadrangle inequality. The
+/// middle i is solved first, and the optimal j splits the candidates of the two halves.
+/// A None cost or previous value is an infinite one.
+///
+/// # Arguments
+///
+/// * `previous`: the values of the previous layer
+/// * `m`: the size of the new layer
+/// * `cost`: the cost of the transition from j to i
+///
+/// returns: Vec<Option<i64>>
+/// \theta((m + n) \log m) evaluations of cost
+pub fn divide_and_conquer_layer<C>(previous: &[Option<i64>], m: usize, cost: C) -> Vec<Option<i64>>
+where
+    C: Fn(usize, usize) -> Option<i64>,
+{
+    let mut next = vec![None; m];
+    if m == 0 || previous.is_empty() {
+        return next;
+    }
+
+    // (range of i, range of candidate j), all closed
+    let mut stack = vec![((0, m - 1), (0, previous.len() - 1))];
+    while let Some(((lo, hi), (opt_lo, opt_hi))) = stack.pop() {
+        let mid = lo + (hi - lo) / 2;
+        let mut best: Option<(i64, usize)> = None;
+        for (j, value) in previous.iter().enumerate().take(opt_hi + 1).skip(opt_lo) {
+            let (Some(value), Some(c)) = (value, cost(j, mid)) else {
+                continue;
+            };
+            if best.is_none_or(|(b, _)| value + c < b) {
+                best = Some((value + c, j));
+            }
+        }
+        next[mid] = best.map(|(b, _)| b);
+
+        let opt = best.map_or(opt_lo, |(_, j)| j);
+        if mid > lo {
+            stack.push(((lo, mid - 1), (opt_lo, opt)));
+        }
+        if mid < hi {
+            stack.push(((mid + 1, hi), (opt, opt_hi)));
+        }
+    }
+    next
+}
+
+/// Splits 0..n into exactly k non-empty consecutive groups minimizing the total cost, the
+/// group [j, i) costing cost(j, i), with one divide and conquer layer per group.
+///
+/// # Arguments
+///
+/// * `k`: the number of groups
+/// * `n`: the number of elements
+/// * `cost`: the cost of the group [j, i), satisfying the quadrangle inequality
+///
+/// returns: Option<i64>, None if k > n
+/// \theta(k * n \log n) evaluations of cost
+pub fn divide_and_conquer_dp<C>(k: usize, n: usize, cost: C) -> Option<i64>
+where
+    C: Fn(usize, usize) -> i64,
+{
+    // layer[i] is the best split of 0..i in t groups
+    let mut layer = vec![None; n + 1];
+    layer[0] = Some(0);
+    for _ in 0..k {
+        layer = divide_and_conquer_layer(&layer, n + 1, |j, i| (j < i).then(|| cost(j, i)));
+    }
+    layer[n]
+}
+
+/// Knuth-Yao speedup of an interval DP: computes f(i, j) = min over i <= k < j of
+/// f(i, k) + f(k + 1, j) + w(i, j), with f(i, i) = 0, for the intervals of 0..n. When w
+/// satisfies the quadrangle inequality w(a, c) + w(b, d) <= w(a, d) + w(b, c) for
+/// a <= b <= c <= d and is monotone on inclusion, the best split opt(i, j) lies between
+/// opt(i, j - 1) and opt(i + 1, j), and the ranges of each diagonal telescope.
+///
+/// # Arguments
+///
+/// * `n`: the number of elements
+/// * `w`: the cost of merging the interval [i, j]
+///
+/// returns: (i64, Vec<Vec<usize>>), f(0, n - 1) and the best splits
+/// \theta(n^2)
+pub fn knuth_yao<W>(n: usize, w: W) -> (i64, Vec<Vec<usize>>)
+where
+    W: Fn(usize, usize) -> i64,
+{
+    let mut f = vec![vec![0; n]; n];
+    let mut opt = vec![vec![0; n]; n];
+    for (i, row) in opt.iter_mut().enumerate() {
+        row[i] = i;
+    }
+
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length - 1;
+            let low = opt[i][j - 1];
+            let high = opt[i + 1][j].min(j - 1);
+            f[i][j] = i64::MAX;
+            for k in low..=high {
+                let candidate = f[i][k] + f[k + 1][j];
+                if candidate < f[i][j] {
+                    f[i][j] = candidate;
+                    opt[i][j] = k;
+                }
+            }
+            f[i][j] += w(i, j);
+        }
+    }
+
+    (f.first().map_or(0, |row| row[n - 1]), opt)
+}
+
+/// The interval DP of `knuth_yao` without the speedup.
+#[cfg(test)]
+fn naive_merge<W: Fn(usize, usize) -> i64>(n: usize, w: W) -> i64 {
+    let mut f = vec![vec![0; n]; n];
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length - 1;
+            f[i][j] = (i..j).map(|k| f[i][k] + f[k + 1][j]).min().unwrap() + w(i, j);
+        }
+    }
+    f[0][n - 1]
+}
+
+#[cfg(test)]
+fn random_values(rng: &mut Lcg, n: usize, modulus: i64) -> Vec<i64> {
+    (0..n).map(|_| rng.below(modulus as u64) as i64).collect()
+}
+
+#[test]
+pub fn test_monotone_cht() {
+    // No existing DP of the compendium applies: the convex hull trick needs a transition cost
+    // linear in a value of the target state, while rod cutting and the knapsacks use arbitrary
+    // prices per length or weight, and holiday planning sums arbitrary attractions, so instead:
+    // splitting an array in groups, each costing the square of its sum plus a constant:
+    // dp[i] = min over j < i of dp[j] + (s[i] - s[j])^2 + c, the line of j having slope
+    // -2 s[j] and intercept dp[j] + s[j]^2, and the queries s[i] increasing
+    let mut rng = Lcg::new(5);
+    for n in [1, 2, 5, 40] {
+        for c in [0, 7, 1000] {
+            let a: Vec<i64> = random_values(&mut rng, n, 20)
+                .iter()
+                .map(|x| x + 1)
+                .collect();
+            let mut s = vec![0; n + 1];
+            for i in 0..n {
+                s[i + 1] = s[i] + a[i];
+            }
+
+            let mut naive = vec![0; n + 1];
+            for i in 1..=n {
+                naive[i] = (0..i)
+                    .map(|j| naive[j] + (s[i] - s[j]).pow(2) + c)
+                    .min()
+                    .unwrap();
+            }
+
+            let mut dp = vec![0; n + 1];
+            let mut cht = MonotoneCht::new();
+            for i in 1..=n {
+                cht.add_line(Line::new(-2 * s[i - 1], dp[i - 1] + s[i - 1] * s[i - 1]));
+                dp[i] = cht.query_monotone(s[i]).unwrap() + s[i] * s[i] + c;
+            }
+            assert_eq!(dp, naive);
+        }
+    }
+
+    // arbitrary queries on the whole envelope
+    let mut cht = MonotoneCht::default();
+    assert_eq!(cht.query(3), None);
+    let mut lines: Vec<Line> = random_values(&mut rng, 60, 41)
+        .chunks(2)
+        .map(|pair| Line::new(pair[0] - 20, pair[1] * 3 - 50))
+        .collect();
+    lines.sort_by_key(|line| std::cmp::Reverse(line.slope));
+    for line in lines.iter() {
+        cht.add_line(*line);
+    }
+    assert!(cht.len() <= lines.len());
+    for x in -30..30 {
+        let expected = lines.iter().map(|line| line.eval(x)).min();
+        assert_eq!(cht.query(x), expected);
+    }
+}
+
+#[test]
+pub fn test_li_chao_tree() {
+    // As for the convex hull trick, no existing DP of the compendium has linear transitions,
+    // so the tree is checked against the minimum of its lines and on a recurrence of its own
+    let mut rng = Lcg::new(9);
+    let mut tree = LiChaoTree::new(-1000, 1000);
+    assert_eq!(tree.query(0), None);
+
+    let mut lines = Vec::new();
+    for _ in 0..200 {
+        let values = random_values(&mut rng, 3, 2001);
+        let line = Line::new(values[0] / 20 - 50, values[1] - 1000);
+        lines.push(line);
+        tree.add_line(line);
+
+        let x = values[2] - 1000;
+        assert_eq!(tree.query(x), lines.iter().map(|l| l.eval(x)).min());
+    }
+    for x in [-1000, -1, 0, 1, 999, 1000] {
+        assert_eq!(tree.query(x), lines.iter().map(|l| l.eval(x)).min());
+    }
+
+    // dp[i] = min over j < i of dp[j] + b[j] * a[i], with slopes and queries in any order
+    let n = 50;
+    let a = random_values(&mut rng, n, 100);
+    let b: Vec<i64> = random_values(&mut rng, n, 100)
+        .iter()
+        .map(|x| x - 50)
+        .collect();
+    let mut naive = vec![0; n];
+    let mut dp = vec![0; n];
+    let mut tree = LiChaoTree::new(0, 99);
+    for i in 1..n {
+        naive[i] = (0..i).map(|j| naive[j] + b[j] * a[i]).min().unwrap();
+        tree.add_line(Line::new(b[i - 1], dp[i - 1]));
+        dp[i] = tree.query(a[i]).unwrap();
+    }
+    assert_eq!(dp, naive);
+}
+
+#[test]
+pub fn test_divide_and_conquer_dp() {
+    // k groups, each costing the square of its sum
+    let mut rng = Lcg::new(17);
+    let a = random_values(&mut rng, 30, 10);
+    let mut s = vec![0; a.len() + 1];
+    for i in 0..a.len() {
+        s[i + 1] = s[i] + a[i];
+    }
+    let cost = |j: usize, i: usize| (s[i] - s[j]).pow(2);
+
+    let n = a.len();
+    let mut naive: Vec<Option<i64>> = vec![None; n + 1];
+    naive[0] = Some(0);
+    for k in 1..=n + 1 {
+        naive = (0..=n)
+            .map(|i| (0..i).filter_map(|j| Some(naive[j]? + cost(j, i))).min())
+            .collect();
+        assert_eq!(divide_and_conquer_dp(k, n, cost), naive[n]);
+    }
+    assert_eq!(divide_and_conquer_dp(0, 0, cost), Some(0));
+}
+
+#[test]
+pub fn test_divide_and_conquer_holiday_planning() {
+    // holiday planning: table[i][k] = max over j of table[i - 1][k - j] + v_i(j), v_i(j) being
+    // the reward of j days in city i. When the attractions of each city are in non-increasing
+    // order v_i is concave, so the best source k - j is monotone in k
+    let mut rng = Lcg::new(23);
+    let (n, d) = (6, 25);
+    let cities: Vec<Vec<i64>> = (0..n)
+        .map(|_| {
+            let mut attractions = random_values(&mut rng, d, 30);
+            attractions.sort_unstable_by(|a, b| b.cmp(a));
+            let mut values = vec![0; d + 1];
+            for j in 0..d {
+                values[j + 1] = values[j] + attractions[j];
+            }
+            values
+        })
+        .collect();
+
+    // the quadratic version of handson3
+    let mut table = vec![vec![0; d + 1]; n + 1];
+    for i in 1..=n {
+        for j in 0..=d {
+            for k in j..=d {
+                table[i][k] = table[i][k].max(table[i - 1][k - j] + cities[i - 1][j]);
+            }
+        }
+    }
+
+    // minimizing the negated rewards
+    let mut layer: Vec<Option<i64>> = vec![Some(0); d + 1];
+    for values in cities.iter() {
+        layer = divide_and_conquer_layer(&layer, d + 1, |source, k| {
+            (source <= k).then(|| -values[k - source])
+        });
+    }
+    assert_eq!(layer[d], Some(-table[n][d]));
+}
+
+#[test]
+pub fn test_knuth_yao() {
+    let mut rng = Lcg::new(3);
+    for n in 1..30 {
+        // sums of non-negative weights satisfy the quadrangle inequality
+        let weights = random_values(&mut rng, n, 50);
+        let w = |i: usize, j: usize| weights[i..=j].iter().sum::<i64>();
+        assert_eq!(knuth_yao(n, w).0, naive_merge(n, w));
+    }
+    assert_eq!(knuth_yao(0, |_, _| 1).0, 0);
+}
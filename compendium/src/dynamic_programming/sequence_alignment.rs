@@ -0,0 +1,473 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::ops::Range;
+
+/// Returns the row of the LCS table for the whole `a`: row[j] is the length of the longest
+/// common subsequence of a and b[..j]. Only two rows are kept.
+fn lcs_row<T: Eq>(a: &[T], b: &[T]) -> Vec<usize> {
+    let mut previous = vec![0; b.len() + 1];
+    let mut current = vec![0; b.len() + 1];
+    for x in a {
+        for (j, y) in b.iter().enumerate() {
+            current[j + 1] = if x == y {
+                previous[j] + 1
+            } else {
+                previous[j + 1].max(current[j])
+            };
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous
+}
+
+/// Returns the length of the longest common subsequence of two sequences.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: usize
+/// \theta(n * m) time, \theta(min(n, m)) memory
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    if a.len() < b.len() {
+        return lcs_length(b, a);
+    }
+    lcs_row(a, b)[b.len()]
+}
+
+/// Returns one longest common subsequence of two sequences as pairs of matched indices
+/// (i, j) with a[i] == b[j], in increasing order. The whole table is kept to walk it back.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: Vec<(usize, usize)>
+/// \theta(n * m)
+pub fn lcs_indices<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let mut t = vec![vec![0; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            t[i][j] = if a[i - 1] == b[j - 1] {
+                t[i - 1][j - 1] + 1
+            } else {
+                t[i - 1][j].max(t[i][j - 1])
+            };
+        }
+    }
+
+    let mut matches = Vec::with_capacity(t[n][m]);
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            matches.push((i - 1, j - 1));
+            i -= 1;
+            j -= 1;
+        } else if t[i - 1][j] >= t[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    matches.reverse();
+    matches
+}
+
+/// Hirschberg's algorithm: returns one longest common subsequence as pairs of matched indices
+/// in linear memory. The first half of `a` is aligned forward and the second half backward
+/// against `b`, the split point of `b` maximizing the sum of the two rows is on an optimal
+/// path, and the two halves are solved recursively.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: Vec<(usize, usize)>
+/// \theta(n * m) time, \theta(n + m) memory
+pub fn hirschberg<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let mut matches = Vec::new();
+    hirschberg_rec(a, b, 0, 0, &mut matches);
+    matches
+}
+
+fn hirschberg_rec<T: Eq>(
+    a: &[T],
+    b: &[T],
+    a_offset: usize,
+    b_offset: usize,
+    matches: &mut Vec<(usize, usize)>,
+) {
+    if a.is_empty() || b.is_empty() {
+        return;
+    }
+    if a.len() == 1 {
+        if let Some(j) = b.iter().position(|y| *y == a[0]) {
+            matches.push((a_offset, b_offset + j));
+        }
+        return;
+    }
+
+    let mid = a.len() / 2;
+    let split = split_point(&a[..mid], &a[mid..], b);
+
+    hirschberg_rec(&a[..mid], &b[..split], a_offset, b_offset, matches);
+    hirschberg_rec(
+        &a[mid..],
+        &b[split..],
+        a_offset + mid,
+        b_offset + split,
+        matches,
+    );
+}
+
+/// Returns the split point of `b` on an optimal path through the end of `first`, `a` being
+/// `first` followed by `second`. The rows are dropped on return, so that they are not kept
+/// alive along the recursion of `hirschberg_rec`.
+fn split_point<T: Eq>(first: &[T], second: &[T], b: &[T]) -> usize {
+    let forward = lcs_row(first, b);
+    // backward[t] is the LCS of second and the last t elements of b
+    let second_reversed: Vec<&T> = second.iter().rev().collect();
+    let b_reversed: Vec<&T> = b.iter().rev().collect();
+    let backward = lcs_row(&second_reversed, &b_reversed);
+
+    let m = b.len();
+    (0..=m)
+        .max_by_key(|&j| (forward[j] + backward[m - j], std::cmp::Reverse(j)))
+        .unwrap()
+}
+
+/// A run of a diff between two sequences.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffRun {
+    /// a[a] and b[b] are equal.
+    Common { a: Range<usize>, b: Range<usize> },
+    /// a[range] is not in b.
+    Deleted(Range<usize>),
+    /// b[range] is not in a.
+    Inserted(Range<usize>),
+}
+
+/// Returns the diff of two sequences, as maximal runs of elements common to both, deleted from
+/// `a` or inserted from `b`, computed on a longest common subsequence. Between two common runs
+/// deletions come before insertions.
+///
+/// # Arguments
+///
+/// * `a`: the old sequence
+/// * `b`: the new sequence
+///
+/// returns: Vec<DiffRun>
+/// \theta(n * m) time, \theta(n + m) memory
+pub fn diff<T: Eq>(a: &[T], b: &[T]) -> Vec<DiffRun> {
+    let mut runs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    let mut matches = hirschberg(a, b).into_iter().peekable();
+    // a sentinel match past the end flushes the last unmatched elements
+    while i < a.len() || j < b.len() {
+        let (next_i, next_j) = matches.peek().copied().unwrap_or((a.len(), b.len()));
+        if i < next_i {
+            runs.push(DiffRun::Deleted(i..next_i));
+        }
+        if j < next_j {
+            runs.push(DiffRun::Inserted(j..next_j));
+        }
+        (i, j) = (next_i, next_j);
+
+        let start = (i, j);
+        while matches.peek() == Some(&(i, j)) {
+            matches.next();
+            i += 1;
+            j += 1;
+        }
+        if i > start.0 {
+            runs.push(DiffRun::Common {
+                a: start.0..i,
+                b: start.1..j,
+            });
+        }
+    }
+
+    runs
+}
+
+/// A step of an alignment script turning a sequence `a` into a sequence `b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// a[i] is kept as b[j].
+    Keep(usize, usize),
+    /// a[i] is replaced by b[j].
+    Substitute(usize, usize),
+    /// a[i] is deleted.
+    Delete(usize),
+    /// b[j] is inserted.
+    Insert(usize),
+    /// a[i] and a[i + 1] are swapped, becoming b[j] and b[j + 1].
+    Transpose(usize, usize),
+}
+
+/// Returns the Levenshtein distance of two sequences: the minimum number of insertions,
+/// deletions and substitutions turning one into the other.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: usize
+/// \theta(n * m) time, \theta(min(n, m)) memory
+pub fn levenshtein<T: Eq>(a: &[T], b: &[T]) -> usize {
+    if a.len() < b.len() {
+        return levenshtein(b, a);
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != y);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Returns the Levenshtein distance of two sequences with an optimal alignment script.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: (usize, Vec<Edit>)
+/// \theta(n * m)
+pub fn levenshtein_script<T: Eq>(a: &[T], b: &[T]) -> (usize, Vec<Edit>) {
+    edit_script(a, b, false)
+}
+
+/// Returns the Damerau-Levenshtein distance of two sequences, in its optimal string alignment
+/// form: the minimum number of insertions, deletions, substitutions and transpositions of two
+/// adjacent elements, where no element is edited more than once.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: usize
+/// \theta(n * m) time, \theta(min(n, m)) memory
+pub fn damerau_levenshtein<T: Eq>(a: &[T], b: &[T]) -> usize {
+    if a.len() < b.len() {
+        return damerau_levenshtein(b, a);
+    }
+
+    // a transposition looks two rows back
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 0..a.len() {
+        current[0] = i + 1;
+        for j in 0..b.len() {
+            let substitution = previous[j] + usize::from(a[i] != b[j]);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            if i > 0 && j > 0 && a[i] == b[j - 1] && a[i - 1] == b[j] {
+                current[j + 1] = current[j + 1].min(before[j - 1] + 1);
+            }
+        }
+        std::mem::swap(&mut before, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Returns the Damerau-Levenshtein distance (optimal string alignment) of two sequences with
+/// an optimal alignment script.
+///
+/// # Arguments
+///
+/// * `a`: the first sequence
+/// * `b`: the second sequence
+///
+/// returns: (usize, Vec<Edit>)
+/// \theta(n * m)
+pub fn damerau_levenshtein_script<T: Eq>(a: &[T], b: &[T]) -> (usize, Vec<Edit>) {
+    edit_script(a, b, true)
+}
+
+/// Fills the whole edit distance table, then walks it back from the bottom right corner
+/// following any move that explains the value of the current cell.
+fn edit_script<T: Eq>(a: &[T], b: &[T], transpositions: bool) -> (usize, Vec<Edit>) {
+    let (n, m) = (a.len(), b.len());
+    let can_transpose = |i: usize, j: usize| {
+        transpositions && i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1]
+    };
+
+    let mut t = vec![vec![0; m + 1]; n + 1];
+    for (i, row) in t.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in t[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution = t[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            t[i][j] = substitution.min(t[i - 1][j] + 1).min(t[i][j - 1] + 1);
+            if can_transpose(i, j) {
+                t[i][j] = t[i][j].min(t[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    let mut script = Vec::new();
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && t[i][j] == t[i - 1][j - 1] {
+            script.push(Edit::Keep(i - 1, j - 1));
+            (i, j) = (i - 1, j - 1);
+        } else if can_transpose(i, j) && t[i][j] == t[i - 2][j - 2] + 1 {
+            script.push(Edit::Transpose(i - 2, j - 2));
+            (i, j) = (i - 2, j - 2);
+        } else if i > 0 && j > 0 && t[i][j] == t[i - 1][j - 1] + 1 {
+            script.push(Edit::Substitute(i - 1, j - 1));
+            (i, j) = (i - 1, j - 1);
+        } else if i > 0 && t[i][j] == t[i - 1][j] + 1 {
+            script.push(Edit::Delete(i - 1));
+            i -= 1;
+        } else {
+            script.push(Edit::Insert(j - 1));
+            j -= 1;
+        }
+    }
+    script.reverse();
+
+    (t[n][m], script)
+}
+
+/// Applies an alignment script to `a`, returning the resulting sequence and the cost.
+#[cfg(test)]
+fn apply<T: Clone>(a: &[T], b: &[T], script: &[Edit]) -> (Vec<T>, usize) {
+    let mut result = Vec::new();
+    let mut cost = 0;
+    for &edit in script {
+        match edit {
+            Edit::Keep(i, _) => result.push(a[i].clone()),
+            Edit::Substitute(_, j) | Edit::Insert(j) => {
+                result.push(b[j].clone());
+                cost += 1;
+            }
+            Edit::Delete(_) => cost += 1,
+            Edit::Transpose(i, _) => {
+                result.push(a[i + 1].clone());
+                result.push(a[i].clone());
+                cost += 1;
+            }
+        }
+    }
+    (result, cost)
+}
+
+#[test]
+pub fn test_lcs() {
+    let a: Vec<char> = "AGGTAB".chars().collect();
+    let b: Vec<char> = "GXTXAYB".chars().collect();
+    assert_eq!(lcs_length(&a, &b), 4);
+    for matches in [lcs_indices(&a, &b), hirschberg(&a, &b)] {
+        let common: String = matches.iter().map(|&(i, _)| a[i]).collect();
+        assert_eq!(common, "GTAB");
+        assert!(matches.iter().all(|&(i, j)| a[i] == b[j]));
+    }
+
+    let a: Vec<char> = "日本語のテキスト".chars().collect();
+    let b: Vec<char> = "日語テスト".chars().collect();
+    assert_eq!(lcs_length(&a, &b), 5);
+    assert_eq!(lcs_length::<u8>(&[], b"abc"), 0);
+}
+
+#[test]
+pub fn test_lcs_brute_force() {
+    let mut rng = Lcg::new(5);
+    for _ in 0..200 {
+        let mut next =
+            |len: usize| -> Vec<u8> { (0..len).map(|_| b"abc"[rng.below(3) as usize]).collect() };
+        let a = next(7);
+        let b = next(9);
+
+        let length = lcs_length(&a, &b);
+        assert_eq!(length, lcs_length(&b, &a));
+        for matches in [lcs_indices(&a, &b), hirschberg(&a, &b)] {
+            assert_eq!(matches.len(), length);
+            assert!(matches
+                .windows(2)
+                .all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+            assert!(matches.iter().all(|&(i, j)| a[i] == b[j]));
+        }
+
+        let (distance, script) = damerau_levenshtein_script(&a, &b);
+        assert_eq!(distance, damerau_levenshtein(&a, &b));
+        assert_eq!(apply(&a, &b, &script), (b.clone(), distance));
+        let (distance, script) = levenshtein_script(&a, &b);
+        assert_eq!(distance, levenshtein(&a, &b));
+        assert_eq!(apply(&a, &b, &script), (b.clone(), distance));
+        // insertions and deletions alone cost n + m - 2 lcs
+        assert!(distance <= a.len() + b.len() - 2 * length);
+    }
+}
+
+#[test]
+pub fn test_edit_distance() {
+    assert_eq!(levenshtein(b"kitten", b"sitting"), 3);
+    assert_eq!(levenshtein(b"", b"abc"), 3);
+    assert_eq!(levenshtein(b"abcd", b"acbd"), 2);
+    assert_eq!(damerau_levenshtein(b"abcd", b"acbd"), 1);
+    // optimal string alignment does not edit the transposed pair again
+    assert_eq!(damerau_levenshtein(b"ca", b"abc"), 3);
+
+    let a: Vec<char> = "héllo".chars().collect();
+    let b: Vec<char> = "hello".chars().collect();
+    assert_eq!(levenshtein(&a, &b), 1);
+
+    let (distance, script) = damerau_levenshtein_script(b"abcd", b"bacde");
+    assert_eq!(distance, 2);
+    assert_eq!(
+        script,
+        vec![
+            Edit::Transpose(0, 0),
+            Edit::Keep(2, 2),
+            Edit::Keep(3, 3),
+            Edit::Insert(4)
+        ]
+    );
+}
+
+#[test]
+pub fn test_diff() {
+    let a = ["fn main() {", "    let x = 1;", "    println!(x);", "}"];
+    let b = [
+        "fn main() {",
+        "    let x = 2;",
+        "    println!(x);",
+        "    x",
+        "}",
+    ];
+    assert_eq!(
+        diff(&a, &b),
+        vec![
+            DiffRun::Common { a: 0..1, b: 0..1 },
+            DiffRun::Deleted(1..2),
+            DiffRun::Inserted(1..2),
+            DiffRun::Common { a: 2..3, b: 2..3 },
+            DiffRun::Inserted(3..4),
+            DiffRun::Common { a: 3..4, b: 4..5 },
+        ]
+    );
+    assert_eq!(diff::<u8>(b"", b"ab"), vec![DiffRun::Inserted(0..2)]);
+    assert_eq!(diff::<u8>(b"ab", b""), vec![DiffRun::Deleted(0..2)]);
+    assert_eq!(diff::<u8>(b"", b""), vec![]);
+}
@@ -1,4 +1,10 @@
-/// Computes the Longest Common Subsequence of two strings.
+use crate::dynamic_programming::sequence_alignment::lcs_length;
+use crate::utilities::coordinate_compression::CoordinateCompressor;
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::cmp::Reverse;
+
+/// Computes the Longest Common Subsequence of two strings, compared char by char.
 ///
 /// # Arguments
 ///
@@ -8,75 +14,372 @@
 /// returns: usize
 /// \theta(n * m)
 pub fn longest_common_subsequence(s1: &str, s2: &str) -> usize {
-    let n = s1.len();
-    let m = s2.len();
-    let mut t = vec![vec![0; m + 1]; n + 1];
-    for i in 1..=n {
-        for j in 1..=m {
-            if s1.chars().nth(i - 1) == s2.chars().nth(j - 1) {
-                t[i][j] = t[i - 1][j - 1] + 1;
-            } else {
-                t[i][j] = t[i - 1][j].max(t[i][j - 1]);
-            }
+    let s1: Vec<char> = s1.chars().collect();
+    let s2: Vec<char> = s2.chars().collect();
+    lcs_length(&s1, &s2)
+}
+
+/// Whether consecutive elements of a subsequence must be strictly increasing or may be equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Monotonicity {
+    Strict,
+    NonStrict,
+}
+
+impl Monotonicity {
+    /// Returns whether b can follow a in an increasing subsequence.
+    fn precedes<T: Ord>(self, a: &T, b: &T) -> bool {
+        match self {
+            Monotonicity::Strict => a < b,
+            Monotonicity::NonStrict => a <= b,
+        }
+    }
+}
+
+/// Patience sorting: tails[k] is the index of the smallest element ending an increasing
+/// subsequence of length k + 1, and the tails are sorted, so each element finds the longest
+/// subsequence it extends with a binary search. Returns, for every element, the length of the
+/// longest increasing subsequence ending in it and the previous element of that subsequence.
+fn patience_sorting<T: Ord>(s: &[T], mode: Monotonicity) -> (Vec<usize>, Vec<Option<usize>>) {
+    let mut tails: Vec<usize> = Vec::new();
+    let mut lengths = Vec::with_capacity(s.len());
+    let mut parents = Vec::with_capacity(s.len());
+
+    for (i, x) in s.iter().enumerate() {
+        let k = tails.partition_point(|&j| mode.precedes(&s[j], x));
+        lengths.push(k + 1);
+        parents.push(k.checked_sub(1).map(|k| tails[k]));
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
         }
     }
-    t[n][m]
+
+    (lengths, parents)
 }
 
-/// Returns the length of the longest increasing subsequence in a vector.
+/// Follows the parents from the given element, returning the indices of the subsequence in
+/// increasing order.
+fn follow_parents(parents: &[Option<usize>], last: usize) -> Vec<usize> {
+    let mut indices = vec![last];
+    while let Some(previous) = parents[*indices.last().unwrap()] {
+        indices.push(previous);
+    }
+    indices.reverse();
+    indices
+}
+
+/// Returns, for every element, the length of the longest increasing subsequence ending in it.
+///
+/// # Arguments
+///
+/// * `s`: the sequence
+/// * `mode`: whether the subsequence must be strictly increasing
+///
+/// returns: Vec<usize>
+/// \theta(n \log n)
+pub fn lis_lengths<T: Ord>(s: &[T], mode: Monotonicity) -> Vec<usize> {
+    patience_sorting(s, mode).0
+}
+
+/// Returns, for every element, the length of the longest strictly increasing subsequence
+/// ending in it.
 ///
 /// # Arguments
 ///
 /// * `s`: the vector
 ///
-/// returns: usize
-/// \theta(n^2)
+/// returns: Vec<usize>
+/// \theta(n \log n)
 pub fn lis(s: Vec<i32>) -> Vec<usize> {
-    let n = s.len();
+    lis_lengths(&s, Monotonicity::Strict)
+}
 
-    let mut t = vec![1; n];
-    for i in 1..n {
-        let mut max = usize::MIN;
-        for j in 0..i {
-            if s[j] < s[i] {
-                max = max.max(t[j]);
+/// Returns the length of the longest strictly increasing subsequence in a vector.
+///
+/// # Arguments
+///
+/// * `s`: the vector
+///
+/// returns: usize
+/// \theta(n \log n)
+pub fn longest_increasing_subsequence(s: Vec<i32>) -> usize {
+    lis_lengths(&s, Monotonicity::Strict)
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+}
+
+/// Returns the indices of one longest increasing subsequence, in increasing order.
+///
+/// # Arguments
+///
+/// * `s`: the sequence
+/// * `mode`: whether the subsequence must be strictly increasing
+///
+/// returns: Vec<usize>
+/// \theta(n \log n)
+pub fn longest_increasing_subsequence_indices<T: Ord>(s: &[T], mode: Monotonicity) -> Vec<usize> {
+    let (lengths, parents) = patience_sorting(s, mode);
+    match (0..s.len()).max_by_key(|&i| lengths[i]) {
+        Some(last) => follow_parents(&parents, last),
+        None => Vec::new(),
+    }
+}
+
+/// Counts the longest increasing subsequences (as sets of indices) modulo p.
+/// The elements ending a subsequence of length k are kept in order of arrival, and their
+/// values never increase, so the ones that can precede a new element are a suffix of the list,
+/// found by binary search, and their counts are summed with prefix sums.
+///
+/// # Arguments
+///
+/// * `s`: the sequence
+/// * `mode`: whether the subsequences must be strictly increasing
+/// * `p`: the modulus, usually a large prime
+///
+/// returns: u64
+/// \theta(n \log n)
+pub fn count_longest_increasing_subsequences<T: Ord>(s: &[T], mode: Monotonicity, p: u64) -> u64 {
+    assert!(p > 0, "Count LIS: the modulus must be positive");
+
+    let lengths = lis_lengths(s, mode);
+    // levels[k] are the elements ending a subsequence of length k + 1, prefix[k] the prefix
+    // sums of the number of such subsequences ending in each of them
+    let mut levels: Vec<Vec<usize>> = Vec::new();
+    let mut prefix: Vec<Vec<u64>> = Vec::new();
+
+    for (i, x) in s.iter().enumerate() {
+        let k = lengths[i] - 1;
+        let count = match k {
+            0 => 1 % p,
+            _ => {
+                let first = levels[k - 1].partition_point(|&j| !mode.precedes(&s[j], x));
+                let sums = &prefix[k - 1];
+                (sums[sums.len() - 1] + p - sums[first]) % p
             }
+        };
+
+        if k == levels.len() {
+            levels.push(Vec::new());
+            prefix.push(vec![0]);
         }
-        t[i] = 1 + max;
+        levels[k].push(i);
+        let total = *prefix[k].last().unwrap();
+        prefix[k].push((total + count) % p);
     }
 
-    t
-}
-
-pub fn longest_increasing_subsequence(s: Vec<i32>) -> usize {
-    let lis = lis(s);
-    *lis.iter().max().unwrap()
+    prefix.last().map_or(1 % p, |sums| *sums.last().unwrap())
 }
 
-/// Returns the length of the longest decreasing subsequence in a vector.
+/// Returns the length of the longest strictly decreasing subsequence in a vector.
 ///
 /// # Arguments
 ///
 /// * `s`: the vector
 ///
 /// returns: usize
-/// \theta(n^2)
-pub fn longest_decreasing_subsequence(mut s: Vec<i32>) -> usize {
-    let reversed = s.iter().copied().rev().collect();
-    longest_increasing_subsequence(reversed)
+/// \theta(n \log n)
+pub fn longest_decreasing_subsequence(s: Vec<i32>) -> usize {
+    let reversed: Vec<Reverse<i32>> = s.into_iter().map(Reverse).collect();
+    lis_lengths(&reversed, Monotonicity::Strict)
+        .into_iter()
+        .max()
+        .unwrap_or(0)
 }
 
+/// Returns the length of the longest strictly bitonic subsequence in a vector, i.e. increasing
+/// and then decreasing.
+///
+/// # Arguments
+///
+/// * `s`: the vector
+///
+/// returns: usize
+/// \theta(n \log n)
 pub fn longest_bitonic_subsequence(s: Vec<i32>) -> usize {
-    let longest_incr_subsequence = lis(s.clone());
-    let longest_decr_subsequence = lis(s.iter().copied().rev().collect());
+    longest_bitonic_subsequence_indices(&s, Monotonicity::Strict).len()
+}
 
+/// Returns the indices of one longest bitonic subsequence, in increasing order. The peak is
+/// the element maximizing the longest increasing subsequence ending in it plus the longest
+/// decreasing subsequence starting from it, the latter being an increasing subsequence of the
+/// reversed sequence.
+///
+/// # Arguments
+///
+/// * `s`: the sequence
+/// * `mode`: whether the two parts must be strictly monotone
+///
+/// returns: Vec<usize>
+/// \theta(n \log n)
+pub fn longest_bitonic_subsequence_indices<T: Ord>(s: &[T], mode: Monotonicity) -> Vec<usize> {
     let n = s.len();
+    let (increasing, increasing_parents) = patience_sorting(s, mode);
+    let reversed: Vec<&T> = s.iter().rev().collect();
+    let (decreasing, decreasing_parents) = patience_sorting(&reversed, mode);
+
+    let Some(peak) = (0..n).max_by_key(|&i| increasing[i] + decreasing[n - 1 - i]) else {
+        return Vec::new();
+    };
+
+    let mut indices = follow_parents(&increasing_parents, peak);
+    let tail = follow_parents(&decreasing_parents, n - 1 - peak);
+    indices.extend(tail.into_iter().rev().skip(1).map(|i| n - 1 - i));
+    indices
+}
+
+/// Returns the indices of one longest chain of points strictly increasing in both coordinates,
+/// in the order of the chain. Sorting by x, and by decreasing y among equal x, leaves at most
+/// one point of each x in a strictly increasing subsequence of the y.
+///
+/// # Arguments
+///
+/// * `points`: the points (x, y)
+///
+/// returns: Vec<usize>
+/// \theta(n \log n)
+pub fn longest_dominance_chain<T: Ord>(points: &[(T, T)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| {
+        let (a, b) = (&points[i], &points[j]);
+        a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1))
+    });
 
-    let mut max = 0;
-    for i in 0..n {
-        max = max.max(longest_incr_subsequence[i] + longest_decr_subsequence[n - i - 1] - 1);
+    let ys: Vec<&T> = order.iter().map(|&i| &points[i].1).collect();
+    longest_increasing_subsequence_indices(&ys, Monotonicity::Strict)
+        .into_iter()
+        .map(|k| order[k])
+        .collect()
+}
+
+/// Fenwick Tree over ranks keeping prefix maxima of (length, position), used by the CDQ sweep.
+struct PrefixMax {
+    tree: Vec<(usize, usize)>,
+}
+
+impl PrefixMax {
+    fn update(&mut self, rank: usize, value: (usize, usize)) {
+        let mut i = rank + 1;
+        while i < self.tree.len() {
+            self.tree[i] = self.tree[i].max(value);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// The maximum over the ranks in [0, rank).
+    fn query(&self, rank: usize) -> (usize, usize) {
+        let mut result = (0, 0);
+        let mut i = rank;
+        while i > 0 {
+            result = result.max(self.tree[i]);
+            i -= i & i.wrapping_neg();
+        }
+        result
+    }
+
+    fn clear(&mut self, rank: usize) {
+        let mut i = rank + 1;
+        while i < self.tree.len() {
+            self.tree[i] = (0, 0);
+            i += i & i.wrapping_neg();
+        }
+    }
+}
+
+/// Returns the indices of one longest chain of points strictly increasing in all the three
+/// coordinates, in the order of the chain, with CDQ divide and conquer. The points are sorted
+/// by x and split in blocks of equal x; the chains ending in the left half are computed first,
+/// then extended to the right half sweeping both halves by y with a Fenwick Tree of prefix
+/// maxima over z, and only then the right half is solved.
+///
+/// # Arguments
+///
+/// * `points`: the points (x, y, z)
+///
+/// returns: Vec<usize>
+/// \theta(n \log^2 n)
+pub fn longest_dominance_chain_3d<T: Ord>(points: &[(T, T, T)]) -> Vec<usize> {
+    let n = points.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| points[i].0.cmp(&points[j].0));
+
+    // the positions in order where a new x starts, and the end
+    let mut blocks: Vec<usize> = (0..n)
+        .filter(|&k| k == 0 || points[order[k - 1]].0 != points[order[k]].0)
+        .collect();
+    blocks.push(n);
+
+    let zs: Vec<&T> = order.iter().map(|&i| &points[i].2).collect();
+    let z_ranks = CoordinateCompressor::new(zs.iter().copied()).compress(&zs);
+
+    // best[k] is the longest chain ending in the point order[k], with the previous position
+    let mut best: Vec<(usize, Option<usize>)> = vec![(1, None); n];
+    let mut fenwick = PrefixMax {
+        tree: vec![(0, 0); n + 1],
+    };
+
+    fn solve<T: Ord>(
+        points: &[(T, T, T)],
+        order: &[usize],
+        blocks: &[usize],
+        z_ranks: &[usize],
+        best: &mut [(usize, Option<usize>)],
+        fenwick: &mut PrefixMax,
+        (lo, hi): (usize, usize),
+    ) {
+        if hi - lo <= 1 {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        solve(points, order, blocks, z_ranks, best, fenwick, (lo, mid));
+
+        let y = |k: &usize| &points[order[*k]].1;
+        let mut left: Vec<usize> = (blocks[lo]..blocks[mid]).collect();
+        let mut right: Vec<usize> = (blocks[mid]..blocks[hi]).collect();
+        left.sort_by(|a, b| y(a).cmp(y(b)));
+        right.sort_by(|a, b| y(a).cmp(y(b)));
+
+        let mut p = 0;
+        for &k in right.iter() {
+            while p < left.len() && y(&left[p]) < y(&k) {
+                fenwick.update(z_ranks[left[p]], (best[left[p]].0, left[p]));
+                p += 1;
+            }
+            let (length, previous) = fenwick.query(z_ranks[k]);
+            if length + 1 > best[k].0 {
+                best[k] = (length + 1, Some(previous));
+            }
+        }
+        for &k in left[..p].iter() {
+            fenwick.clear(z_ranks[k]);
+        }
+
+        solve(points, order, blocks, z_ranks, best, fenwick, (mid, hi));
     }
-    max
+
+    let groups = blocks.len() - 1;
+    solve(
+        points,
+        &order,
+        &blocks,
+        &z_ranks,
+        &mut best,
+        &mut fenwick,
+        (0, groups),
+    );
+
+    let Some(mut k) = (0..n).max_by_key(|&k| best[k].0) else {
+        return Vec::new();
+    };
+    let mut chain = vec![order[k]];
+    while let Some(previous) = best[k].1 {
+        chain.push(order[previous]);
+        k = previous;
+    }
+    chain.reverse();
+    chain
 }
 
 #[test]
@@ -90,6 +393,16 @@ pub fn test_longest_common_subsequence() {
     let s1 = "abc";
     let s2 = "def";
     assert_eq!(longest_common_subsequence(s1, s2), 0);
+    let s1 = "naïve café";
+    let s2 = "naive cafe";
+    assert_eq!(longest_common_subsequence(s1, s2), 8);
+}
+
+#[cfg(test)]
+fn is_monotone<T: Ord>(s: &[T], indices: &[usize], mode: Monotonicity) -> bool {
+    indices
+        .windows(2)
+        .all(|w| w[0] < w[1] && mode.precedes(&s[w[0]], &s[w[1]]))
 }
 
 #[test]
@@ -100,6 +413,71 @@ pub fn test_longest_increasing_subsequence() {
     assert_eq!(longest_increasing_subsequence(s), 4);
     let s = vec![7, 7, 7, 7, 7, 7, 7];
     assert_eq!(longest_increasing_subsequence(s), 1);
+    assert_eq!(longest_increasing_subsequence(vec![]), 0);
+}
+
+#[test]
+pub fn test_longest_increasing_subsequence_modes() {
+    let s = [3, 1, 2, 2, 5, 2, 4];
+    assert_eq!(
+        lis_lengths(&s, Monotonicity::Strict),
+        vec![1, 1, 2, 2, 3, 2, 3]
+    );
+    assert_eq!(
+        lis_lengths(&s, Monotonicity::NonStrict),
+        vec![1, 1, 2, 3, 4, 4, 5]
+    );
+
+    let strict = longest_increasing_subsequence_indices(&s, Monotonicity::Strict);
+    assert_eq!(strict.len(), 3);
+    assert!(is_monotone(&s, &strict, Monotonicity::Strict));
+    let non_strict = longest_increasing_subsequence_indices(&s, Monotonicity::NonStrict);
+    assert_eq!(non_strict, vec![1, 2, 3, 5, 6]);
+
+    let words = ["pear", "apple", "fig", "banana", "kiwi", "cherry"];
+    let indices = longest_increasing_subsequence_indices(&words, Monotonicity::Strict);
+    assert_eq!(indices.len(), 3);
+    assert!(is_monotone(&words, &indices, Monotonicity::Strict));
+}
+
+#[test]
+pub fn test_count_longest_increasing_subsequences() {
+    let p = 1_000_000_007;
+    assert_eq!(
+        count_longest_increasing_subsequences(&[1, 3, 5, 4, 7], Monotonicity::Strict, p),
+        2
+    );
+    assert_eq!(
+        count_longest_increasing_subsequences(&[2, 2, 2, 2, 2], Monotonicity::Strict, p),
+        5
+    );
+    assert_eq!(
+        count_longest_increasing_subsequences(&[2, 2, 2, 2, 2], Monotonicity::NonStrict, p),
+        1
+    );
+
+    // brute force over all the subsets of indices
+    let s = [4, 1, 3, 3, 2, 5, 1, 4, 6, 2, 6];
+    for mode in [Monotonicity::Strict, Monotonicity::NonStrict] {
+        let mut best = 0;
+        let mut count = 0;
+        for mask in 1..1_usize << s.len() {
+            let indices: Vec<usize> = (0..s.len()).filter(|&i| mask >> i & 1 == 1).collect();
+            if is_monotone(&s, &indices, mode) {
+                match indices.len().cmp(&best) {
+                    std::cmp::Ordering::Greater => (best, count) = (indices.len(), 1),
+                    std::cmp::Ordering::Equal => count += 1,
+                    std::cmp::Ordering::Less => {}
+                }
+            }
+        }
+        assert_eq!(*lis_lengths(&s, mode).iter().max().unwrap(), best);
+        assert_eq!(count_longest_increasing_subsequences(&s, mode, p), count);
+        assert_eq!(
+            count_longest_increasing_subsequences(&s, mode, 2),
+            count % 2
+        );
+    }
 }
 
 #[test]
@@ -107,7 +485,7 @@ pub fn test_longest_decreasing_subsequence() {
     let s = vec![10, 9, 2, 5, 3, 7, 101, 18];
     assert_eq!(longest_decreasing_subsequence(s), 4);
     let s = vec![0, 1, 0, 3, 2, 3];
-    assert_eq!(longest_decreasing_subsequence(s), 4);
+    assert_eq!(longest_decreasing_subsequence(s), 2);
     let s = vec![7, 7, 7, 7, 7, 7, 7];
     assert_eq!(longest_decreasing_subsequence(s), 1);
 }
@@ -121,3 +499,85 @@ pub fn test_longest_bitonic_subsequence() {
     let s = vec![80, 60, 30, 40, 20, 10];
     assert_eq!(longest_bitonic_subsequence(s), 5);
 }
+
+#[test]
+pub fn test_longest_bitonic_subsequence_indices() {
+    let s = [1, 11, 2, 10, 4, 5, 2, 1];
+    let indices = longest_bitonic_subsequence_indices(&s, Monotonicity::Strict);
+    assert_eq!(indices.len(), 6);
+    let peak = (0..indices.len()).max_by_key(|&i| s[indices[i]]).unwrap();
+    assert!(is_monotone(&s, &indices[..=peak], Monotonicity::Strict));
+    let descending: Vec<_> = indices[peak..].iter().map(|&i| Reverse(s[i])).collect();
+    assert!(is_monotone(
+        &descending,
+        &(0..descending.len()).collect::<Vec<_>>(),
+        Monotonicity::Strict
+    ));
+
+    let plateau = [1, 2, 2, 3, 3, 1];
+    assert_eq!(
+        longest_bitonic_subsequence_indices(&plateau, Monotonicity::NonStrict),
+        vec![0, 1, 2, 3, 4, 5]
+    );
+    assert!(longest_bitonic_subsequence_indices::<i32>(&[], Monotonicity::Strict).is_empty());
+}
+
+/// The length of the longest chain, with the quadratic DP.
+#[cfg(test)]
+fn naive_chain_lengths<P>(points: &[P], precedes: impl Fn(&P, &P) -> bool) -> usize {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    // a topological order: the predecessors of a point have less predecessors
+    order.sort_by_key(|&i| points.iter().filter(|p| precedes(p, &points[i])).count());
+    let mut lengths = vec![1; points.len()];
+    for (a, &i) in order.iter().enumerate() {
+        for &j in order[..a].iter() {
+            if precedes(&points[j], &points[i]) {
+                lengths[i] = lengths[i].max(lengths[j] + 1);
+            }
+        }
+    }
+    lengths.into_iter().max().unwrap_or(0)
+}
+
+#[test]
+pub fn test_longest_dominance_chain() {
+    let points = [(1, 5), (2, 3), (2, 4), (3, 4), (4, 6), (0, 0)];
+    assert_eq!(longest_dominance_chain(&points), vec![5, 1, 3, 4]);
+    assert!(longest_dominance_chain::<i32>(&[]).is_empty());
+
+    let mut rng = Lcg::new(77);
+    let mut random = |modulus: u64| rng.below(modulus) as i32;
+    for n in [1, 2, 10, 60] {
+        let points: Vec<(i32, i32)> = (0..n).map(|_| (random(8), random(8))).collect();
+        let precedes = |a: &(i32, i32), b: &(i32, i32)| a.0 < b.0 && a.1 < b.1;
+        let chain = longest_dominance_chain(&points);
+        assert_eq!(chain.len(), naive_chain_lengths(&points, precedes));
+        assert!(chain
+            .windows(2)
+            .all(|w| precedes(&points[w[0]], &points[w[1]])));
+    }
+}
+
+#[test]
+pub fn test_longest_dominance_chain_3d() {
+    let points = [(1, 1, 1), (2, 2, 0), (2, 3, 3), (3, 4, 4), (3, 0, 5)];
+    assert_eq!(longest_dominance_chain_3d(&points), vec![0, 2, 3]);
+    assert!(longest_dominance_chain_3d::<i32>(&[]).is_empty());
+
+    let mut rng = Lcg::new(91);
+    let mut random = |modulus: u64| rng.below(modulus) as i32;
+    for n in [1, 2, 7, 30, 120] {
+        for range in [3, 10, 50] {
+            let points: Vec<(i32, i32, i32)> = (0..n)
+                .map(|_| (random(range), random(range), random(range)))
+                .collect();
+            let precedes =
+                |a: &(i32, i32, i32), b: &(i32, i32, i32)| a.0 < b.0 && a.1 < b.1 && a.2 < b.2;
+            let chain = longest_dominance_chain_3d(&points);
+            assert_eq!(chain.len(), naive_chain_lengths(&points, precedes));
+            assert!(chain
+                .windows(2)
+                .all(|w| precedes(&points[w[0]], &points[w[1]])));
+        }
+    }
+}
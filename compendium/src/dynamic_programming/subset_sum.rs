@@ -0,0 +1,287 @@
+use crate::utilities::bitset::BitSet;
+
+/// The sums of all the subsets of an array, stored as a bitset over the range of the possible
+/// sums [sum of the negative elements, sum of the positive elements]. Adding an element x
+/// is a single shift-or of the bitset, so the whole set is built with n * S / 64 word
+/// operations, where S is the size of the range.
+pub struct SubsetSums {
+    min: i64,
+    reachable: BitSet,
+}
+
+impl SubsetSums {
+    /// Computes the sums of all the subsets of s.
+    ///
+    /// # Arguments
+    ///
+    /// * `s`: the input array, possibly with negative elements
+    ///
+    /// returns: SubsetSums
+    /// \theta(n * S / 64)
+    pub fn new(s: &[i64]) -> Self {
+        let (min, max) = sum_range(s);
+        let mut reachable = BitSet::new((max - min) as usize + 1);
+        reachable.set(-min as usize);
+        for &x in s {
+            add_element(&mut reachable, x);
+        }
+        Self { min, reachable }
+    }
+
+    pub fn contains(&self, target: i64) -> bool {
+        match self.index(target) {
+            Some(i) => self.reachable.get(i),
+            None => false,
+        }
+    }
+
+    /// Returns the sums of the subsets in increasing order.
+    pub fn sums(&self) -> impl Iterator<Item = i64> + '_ {
+        self.reachable.iter_ones().map(|i| self.min + i as i64)
+    }
+
+    fn index(&self, target: i64) -> Option<usize> {
+        let i = target.checked_sub(self.min)?;
+        if 0 <= i && (i as usize) < self.reachable.len() {
+            Some(i as usize)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the minimum and the maximum sum of a subset of s.
+fn sum_range(s: &[i64]) -> (i64, i64) {
+    let min = s.iter().filter(|&&x| x < 0).sum();
+    let max = s.iter().filter(|&&x| x > 0).sum();
+    (min, max)
+}
+
+/// Adds the element x to every sum in the bitset, keeping the old sums.
+fn add_element(reachable: &mut BitSet, x: i64) {
+    if x >= 0 {
+        reachable.or_shifted_left(x as usize);
+    } else {
+        reachable.or_shifted_right(x.unsigned_abs() as usize);
+    }
+}
+
+/// Given an array of integers, returns whether there is a subset with sum v.
+///
+/// # Arguments
+///
+/// * `s`: the input array
+/// * `v`: the target sum
+///
+/// returns: bool
+/// \theta(n * S / 64)
+pub fn subset_sum(s: Vec<i32>, v: usize) -> bool {
+    let s: Vec<i64> = s.into_iter().map(i64::from).collect();
+    SubsetSums::new(&s).contains(v as i64)
+}
+
+/// Returns the indices of a subset of s with the given sum, in increasing order, if any.
+/// The bitset after every prefix is kept, so the last element of a solution is an element
+/// without which the target was not reachable.
+///
+/// # Arguments
+///
+/// * `s`: the input array, possibly with negative elements
+/// * `target`: the target sum
+///
+/// returns: Option<Vec<usize>>
+/// \theta(n * S / 64) time, \theta(n * S / 64) memory
+pub fn subset_sum_witness(s: &[i64], target: i64) -> Option<Vec<usize>> {
+    let (min, max) = sum_range(s);
+    if target < min || target > max {
+        return None;
+    }
+
+    // prefixes[i] are the sums of the subsets of the first i elements
+    let mut prefixes = Vec::with_capacity(s.len() + 1);
+    let mut reachable = BitSet::new((max - min) as usize + 1);
+    reachable.set(-min as usize);
+    for &x in s {
+        prefixes.push(reachable.clone());
+        add_element(&mut reachable, x);
+    }
+
+    let mut current = (target - min) as usize;
+    if !reachable.get(current) {
+        return None;
+    }
+
+    let mut subset = Vec::new();
+    for i in (0..s.len()).rev() {
+        if !prefixes[i].get(current) {
+            subset.push(i);
+            current = (current as i64 - s[i]) as usize;
+        }
+    }
+    subset.reverse();
+    Some(subset)
+}
+
+/// Meet in the middle subset sum for arrays of at most 40 elements with arbitrary values:
+/// the sums of the two halves are enumerated separately, then for every sum of the first half
+/// the complement is binary searched among the sorted sums of the second half.
+/// Returns the indices of a subset with the given sum, in increasing order, if any.
+///
+/// # Arguments
+///
+/// * `s`: the input array, possibly with negative elements
+/// * `target`: the target sum
+///
+/// returns: Option<Vec<usize>>
+/// \theta(2^{n/2} * n)
+pub fn meet_in_the_middle(s: &[i64], target: i64) -> Option<Vec<usize>> {
+    assert!(s.len() <= 40, "Meet in the Middle: too many elements");
+
+    let half = s.len() / 2;
+    let left = half_sums(&s[..half]);
+    let mut right = half_sums(&s[half..]);
+    right.sort_unstable();
+
+    for (sum, left_mask) in left {
+        let complement = target as i128 - sum;
+        let i = right.partition_point(|&(x, _)| x < complement);
+        if let Some(&(x, right_mask)) = right.get(i) {
+            if x == complement {
+                let mask = left_mask | right_mask << half;
+                return Some((0..s.len()).filter(|&i| mask >> i & 1 == 1).collect());
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the sum of every subset of s with the mask of its elements. Sums are computed on
+/// i128, so they never overflow.
+fn half_sums(s: &[i64]) -> Vec<(i128, u64)> {
+    let mut sums = Vec::with_capacity(1 << s.len());
+    sums.push((0, 0));
+    for (i, &x) in s.iter().enumerate() {
+        for j in 0..sums.len() {
+            let (sum, mask) = sums[j];
+            sums.push((sum + x as i128, mask | 1 << i));
+        }
+    }
+    sums
+}
+
+/// Counts the subsets of s with the given sum, modulo p.
+///
+/// # Arguments
+///
+/// * `s`: the input array, possibly with negative elements
+/// * `target`: the target sum
+/// * `p`: the modulus, usually a large prime
+///
+/// returns: u64
+/// \theta(n * S) time, \theta(S) memory
+pub fn count_subsets(s: &[i64], target: i64, p: u64) -> u64 {
+    assert!(p > 0, "Count Subsets: the modulus must be positive");
+
+    let (min, max) = sum_range(s);
+    if target < min || target > max {
+        return 0;
+    }
+
+    // ways[i] is the number of subsets with sum min + i; every element is read before being
+    // updated by visiting the sums away from the direction of the shift
+    let len = (max - min) as usize + 1;
+    let mut ways = vec![0_u64; len];
+    ways[-min as usize] = 1 % p;
+    for &x in s {
+        let shift = x.unsigned_abs() as usize;
+        if x >= 0 {
+            for i in (shift..len).rev() {
+                ways[i] = (ways[i] + ways[i - shift]) % p;
+            }
+        } else {
+            for i in 0..len - shift {
+                ways[i] = (ways[i] + ways[i + shift]) % p;
+            }
+        }
+    }
+
+    ways[(target - min) as usize]
+}
+
+#[cfg(test)]
+fn brute_force(s: &[i64], target: i64) -> u64 {
+    (0..1_usize << s.len())
+        .filter(|mask| {
+            let sum: i64 = (0..s.len())
+                .filter(|i| mask >> i & 1 == 1)
+                .map(|i| s[i])
+                .sum();
+            sum == target
+        })
+        .count() as u64
+}
+
+#[test]
+pub fn test_subset_sum() {
+    let s = vec![3, 34, 4, 12, 5, 2];
+    assert!(subset_sum(s.clone(), 9));
+    assert!(!subset_sum(s.clone(), 30));
+    assert!(subset_sum(s.clone(), 14));
+    assert!(subset_sum(s, 0));
+    assert!(subset_sum(vec![-7, 3, 12], 5));
+}
+
+#[test]
+pub fn test_subset_sums_brute_force() {
+    let s = [5, -3, 8, 0, -11, 4, 7, -2, 1];
+    let sums = SubsetSums::new(&s);
+    let all: Vec<i64> = sums.sums().collect();
+
+    for target in -20..30 {
+        let count = brute_force(&s, target);
+        assert_eq!(sums.contains(target), count > 0);
+        assert_eq!(all.contains(&target), count > 0);
+        assert_eq!(count_subsets(&s, target, 1_000_000_007), count);
+        assert_eq!(count_subsets(&s, target, 3), count % 3);
+
+        for witness in [
+            subset_sum_witness(&s, target),
+            meet_in_the_middle(&s, target),
+        ] {
+            match witness {
+                None => assert_eq!(count, 0),
+                Some(subset) => {
+                    assert!(subset.windows(2).all(|w| w[0] < w[1]));
+                    assert_eq!(subset.iter().map(|&i| s[i]).sum::<i64>(), target);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_meet_in_the_middle() {
+    let s: Vec<i64> = (0..40_i64)
+        .map(|i| (i * 982_451_653 % 1_000_000_007) * 1_000_000 - i)
+        .collect();
+    let target: i64 = [3, 17, 25, 38].iter().map(|&i| s[i]).sum();
+
+    let subset = meet_in_the_middle(&s, target).unwrap();
+    assert_eq!(subset.iter().map(|&i| s[i]).sum::<i64>(), target);
+    assert_eq!(meet_in_the_middle(&s[..10], -1), None);
+
+    let extremes = [i64::MAX, i64::MAX, i64::MIN, -5];
+    assert_eq!(meet_in_the_middle(&extremes, -5), Some(vec![3]));
+    assert_eq!(meet_in_the_middle(&extremes, -1), Some(vec![0, 2]));
+}
+
+#[test]
+pub fn test_count_subsets() {
+    // 2^50 subsets of fifty ones, C(50, 25) of them with sum 25
+    let ones = [1; 50];
+    assert_eq!(count_subsets(&ones, 25, 1_000_000_007), 605_552_882);
+    assert_eq!(count_subsets(&[0, 0, 0], 0, 1_000_000_007), 8);
+    assert_eq!(count_subsets(&[2, 4], 5, 7), 0);
+    assert_eq!(count_subsets(&[2, 4], 100, 7), 0);
+}
@@ -1,8 +1,5 @@
 use crate::fenwick_trees::fenwick_tree::FenwickTree;
-use std::collections::HashMap;
-use std::fmt::Debug;
-use std::hash::Hash;
-use std::ops::{Add, AddAssign, Sub};
+use crate::utilities::coordinate_compression::CoordinateCompressor;
 
 /// Given an array of n elements, count the number of position i < j such that a[i] > a[j].
 /// Measures the 'unsortedness' of the array.
@@ -11,81 +8,156 @@ use std::ops::{Add, AddAssign, Sub};
 ///
 /// * `a`: the input array
 ///
-/// returns: usize
+/// returns: u64
 /// \theta(n log n)
-pub fn counting_inversions<T>(mut a: Vec<T>) -> T
+pub fn counting_inversions<T>(a: &[T]) -> u64
 where
-    T: Ord
-        + Clone
-        + Add<Output = T>
-        + Sub<Output = T>
-        + From<u8>
-        + Copy
-        + Default
-        + AddAssign
-        + PartialEq
-        + Debug
-        + Hash,
-    usize: From<T>,
+    T: Ord,
 {
-    let n = a.len();
-
-    a = remap(a, n);
+    inversions_per_element(a).iter().sum()
+}
 
-    let mut fenwick_tree = FenwickTree::with_capacity(n);
-    let mut result = T::default();
+/// Given an array of n elements, returns for every position j the number of positions i < j
+/// such that a[i] > a[j], i.e. how many earlier elements are larger than a[j].
+///
+/// # Arguments
+///
+/// * `a`: the input array
+///
+/// returns: Vec<u64, Global>
+/// \theta(n log n)
+pub fn inversions_per_element<T>(a: &[T]) -> Vec<u64>
+where
+    T: Ord,
+{
+    // ranks are shifted by one since the Fenwick Tree is 1-indexed
+    let compressor = CoordinateCompressor::new(a.iter());
+    let mut fenwick_tree: FenwickTree<u64> = FenwickTree::with_capacity(compressor.len());
 
-    for i in 0..n {
-        fenwick_tree.add(usize::from(a[i]), T::from(1));
-        result += fenwick_tree.sum(n) - fenwick_tree.sum(usize::from(a[i]));
-    }
+    a.iter()
+        .enumerate()
+        .map(|(j, v)| {
+            let rank = compressor.rank(&v).unwrap() + 1;
+            let larger = j as u64 - fenwick_tree.sum(rank);
+            fenwick_tree.add(rank, 1);
+            larger
+        })
+        .collect()
+}
 
-    result
+/// Counts the inversions of the array with merge sort, without requiring the elements to be
+/// copied or hashed. Use `sort_counting_inversions` to also get the sorted array back.
+///
+/// # Arguments
+///
+/// * `a`: the input array
+///
+/// returns: u64
+/// \theta(n log n)
+pub fn counting_inversions_merge_sort<T>(a: &[T]) -> u64
+where
+    T: Ord,
+{
+    sort_counting_inversions(a.iter().collect()).1
 }
 
-/// Remap the elements of a vector to the range [1, n] where n is the length of the vector.
+/// Sorts the array with merge sort, counting the inversions while merging: whenever an element
+/// of the right half is placed before the remaining elements of the left half, each of them
+/// forms an inversion with it. The sort is stable.
 ///
 /// # Arguments
 ///
-/// * `a`: the input vector
+/// * `a`: the input array
 ///
-/// returns: ()
+/// returns: (Vec<T, Global>, u64)
 /// \theta(n log n)
-fn remap<T>(mut a: Vec<T>, n: usize) -> Vec<T>
+pub fn sort_counting_inversions<T>(mut a: Vec<T>) -> (Vec<T>, u64)
 where
-    T: Ord
-        + Clone
-        + Add<Output = T>
-        + Sub<Output = T>
-        + From<u8>
-        + Copy
-        + Default
-        + AddAssign
-        + PartialEq
-        + Debug
-        + Hash,
-    usize: From<T>,
+    T: Ord,
 {
-    let mut b = a.clone();
-    b.sort();
-    let mut map = HashMap::new();
-    let mut count = T::from(1);
-    for i in 0..n {
-        if i > 0 && b[i] != b[i - 1] {
-            count += T::from(1);
-        }
-        map.insert(b[i].clone(), count);
+    let n = a.len();
+    if n <= 1 {
+        return (a, 0);
     }
-    for i in 0..n {
-        a[i] = *map.get(&a[i]).unwrap();
+
+    let right = a.split_off(n / 2);
+    let (left, left_inversions) = sort_counting_inversions(a);
+    let (right, right_inversions) = sort_counting_inversions(right);
+
+    let mut inversions = left_inversions + right_inversions;
+    let mut left_remaining = left.len() as u64;
+    let mut merged = Vec::with_capacity(n);
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    while let (Some(x), Some(y)) = (left.peek(), right.peek()) {
+        if y < x {
+            merged.push(right.next().unwrap());
+            inversions += left_remaining;
+        } else {
+            merged.push(left.next().unwrap());
+            left_remaining -= 1;
+        }
     }
-    a
+    merged.extend(left);
+    merged.extend(right);
+
+    (merged, inversions)
 }
 
 #[test]
 pub fn test_counting_inversion() {
     let mut a = vec![2, 3, 8, 6, 1];
-    assert_eq!(counting_inversions(a), 5_usize);
+    assert_eq!(counting_inversions(&a), 5);
     a = vec![1, 20, 6, 4, 5];
-    assert_eq!(counting_inversions(a), 5_usize);
+    assert_eq!(counting_inversions(&a), 5);
+}
+
+#[test]
+pub fn test_counting_inversion_duplicates() {
+    let a = vec![3, 3, 1, 3, 1];
+    assert_eq!(counting_inversions(&a), 5);
+    assert_eq!(counting_inversions_merge_sort(&a), 5);
+}
+
+#[test]
+pub fn test_counting_inversion_generic() {
+    let words: Vec<String> = ["pear", "fig", "apple"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(counting_inversions(&words), 3);
+    assert_eq!(counting_inversions_merge_sort(&words), 3);
+
+    let pairs = vec![(1, 2), (1, 1), (0, 5), (2, 0)];
+    assert_eq!(counting_inversions(&pairs), 3);
+    assert_eq!(counting_inversions_merge_sort(&pairs), 3);
+}
+
+#[test]
+pub fn test_counting_inversion_no_overflow() {
+    // the decreasing array of 300 elements has 300 * 299 / 2 = 44850 inversions, more than
+    // a u8 or an i16 can hold, so the count must not be kept in a small element type
+    let a: Vec<u16> = (0..300).rev().collect();
+    assert_eq!(counting_inversions(&a), 44850);
+    assert_eq!(counting_inversions_merge_sort(&a), 44850);
+}
+
+#[test]
+pub fn test_sort_counting_inversions() {
+    let (sorted, inversions) = sort_counting_inversions(vec![2, 3, 8, 6, 1]);
+    assert_eq!(sorted, vec![1, 2, 3, 6, 8]);
+    assert_eq!(inversions, 5);
+
+    let (sorted, inversions) = sort_counting_inversions(Vec::<i32>::new());
+    assert!(sorted.is_empty());
+    assert_eq!(inversions, 0);
+}
+
+#[test]
+pub fn test_inversions_per_element() {
+    let a = vec![2, 3, 8, 6, 1];
+    assert_eq!(inversions_per_element(&a), vec![0, 0, 0, 1, 4]);
+    let a = vec![3, 3, 1, 3, 1];
+    assert_eq!(inversions_per_element(&a), vec![0, 0, 2, 0, 3]);
 }
@@ -1,8 +1,11 @@
 use crate::fenwick_trees::fenwick_tree::FenwickTree;
+use crate::utilities::coordinate_compression::CoordinateCompressor;
+use std::cmp::Reverse;
 
 /// Given a set of n segments, for each segments count the number of smaller segments that it
-/// contains. The right endpoint of each segment is unique. We assume that each segment is such that
-/// (l, r) subset of [1, 2n] and l < r.
+/// contains. The right endpoint of each segment is unique. Endpoints can be arbitrary integers
+/// since right endpoints are compressed before indexing the Fenwick Tree.
+/// The i-th element of the result refers to the i-th input segment.
 /// https://codeforces.com/problemset/problem/652/D?locale=en
 ///
 /// # Arguments
@@ -12,21 +15,26 @@ use crate::fenwick_trees::fenwick_tree::FenwickTree;
 /// returns: Vec<i32, Global>
 ///
 /// \theta( n log n)
-pub fn nested_segments(mut segments: Vec<(i32, i32)>) -> Vec<i32> {
+pub fn nested_segments(segments: Vec<(i32, i32)>) -> Vec<i32> {
     let n = segments.len();
-    let mut result = Vec::new();
+    let mut result = vec![0; n];
 
-    segments.sort_by(|a, b| a.0.cmp(&b.0));
+    let compressor = CoordinateCompressor::new(segments.iter().map(|&(_, r)| r));
 
-    let mut fenwick_tree: FenwickTree<i32> = FenwickTree::with_capacity(n);
+    // segments sharing the left endpoint are visited from the largest one
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&i| (segments[i].0, Reverse(segments[i].1)));
+
+    let mut fenwick_tree: FenwickTree<i32> = FenwickTree::with_capacity(compressor.len());
 
     for &(_, r) in &segments {
-        fenwick_tree.add(r as usize, 1);
+        fenwick_tree.add(compressor.rank(&r).unwrap() + 1, 1);
     }
 
-    for &(_, r) in &segments {
-        result.push(fenwick_tree.sum(r as usize - 1));
-        fenwick_tree.add(r as usize, -1);
+    for i in order {
+        let rank = compressor.rank(&segments[i].1).unwrap() + 1;
+        result[i] = fenwick_tree.sum(rank - 1);
+        fenwick_tree.add(rank, -1);
     }
 
     result
@@ -38,3 +46,10 @@ pub fn test_nested_segments() {
     let result = nested_segments(segments);
     assert_eq!(result, vec![2, 1, 0]);
 }
+
+#[test]
+pub fn test_nested_segments_large_coordinates() {
+    let segments = vec![(-1_000_000, 3), (2, 2_000_000_000), (-5, 0), (5, 100)];
+    let result = nested_segments(segments);
+    assert_eq!(result, vec![1, 1, 0, 0]);
+}
@@ -1,38 +1,223 @@
-/// Runs binary search with the given predicate.
+use num_traits::PrimInt;
+
+/// Returns the first x in [lo, hi) such that pred(x) is false, or hi if there is none.
+/// The predicate must be monotone: true on a prefix of the range and false afterwards.
+/// The midpoint is computed without overflow, so the whole range of any integer type
+/// (signed or unsigned) can be searched.
 ///
 /// # Arguments
 ///
-/// * `a`: the input array
-/// * `v`: the value to be searched
-/// * `l`: the starting index of the binary search
-/// * `r`: the ending index of the binary search
-/// * `pred`: the predicate function to compare items
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range (excluded)
+/// * `pred`: the monotone predicate
 ///
-/// returns: (T, usize)
+/// returns: T
 ///
-/// \theta(\log n)
+/// \theta(\log (hi - lo))
+pub fn partition_point<T, F>(mut lo: T, mut hi: T, mut pred: F) -> T
+where
+    T: PrimInt,
+    F: FnMut(T) -> bool,
+{
+    while lo < hi {
+        // floor((lo + hi) / 2) without computing lo + hi
+        let middle = (lo & hi) + ((lo ^ hi) >> 1);
+
+        if pred(middle) {
+            lo = middle + T::one();
+        } else {
+            hi = middle;
+        }
+    }
+
+    lo
+}
 
-pub fn binary_search<T, F>(a: Vec<T>, mut l: usize, mut r: usize, pred: F) -> Option<usize>
+/// Returns the last x in [lo, hi) such that pred(x) is true, if any.
+/// The predicate must be true on a prefix of the range and false afterwards.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range (excluded)
+/// * `pred`: the monotone predicate
+///
+/// returns: Option<T>
+///
+/// \theta(\log (hi - lo))
+pub fn last_true<T, F>(lo: T, hi: T, pred: F) -> Option<T>
 where
-    T: Ord + Copy,
-    F: Fn(T) -> bool,
+    T: PrimInt,
+    F: FnMut(T) -> bool,
 {
-    assert!(r <= a.len(), "Binary Search: end out of range");
+    let point = partition_point(lo, hi, pred);
+    if point > lo {
+        Some(point - T::one())
+    } else {
+        None
+    }
+}
+
+/// Returns the first x in [lo, hi) such that pred(x) is true, if any.
+/// The predicate must be false on a prefix of the range and true afterwards.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range (excluded)
+/// * `pred`: the monotone predicate
+///
+/// returns: Option<T>
+///
+/// \theta(\log (hi - lo))
+pub fn first_true<T, F>(lo: T, hi: T, mut pred: F) -> Option<T>
+where
+    T: PrimInt,
+    F: FnMut(T) -> bool,
+{
+    let point = partition_point(lo, hi, |x| !pred(x));
+    if point < hi {
+        Some(point)
+    } else {
+        None
+    }
+}
+
+/// Returns the first x >= lo such that pred(x) is false, when no upper bound is known.
+/// The range is doubled until pred becomes false, then binary search is run on the last step,
+/// so the cost depends on the distance of the answer from lo. If pred is true up to the
+/// maximum value of T, the maximum value is returned.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `pred`: the monotone predicate
+///
+/// returns: T
+///
+/// \theta(\log (answer - lo))
+pub fn exponential_search<T, F>(lo: T, mut pred: F) -> T
+where
+    T: PrimInt,
+    F: FnMut(T) -> bool,
+{
+    if !pred(lo) {
+        return lo;
+    }
+
+    // invariant: pred(last) is true
+    let mut last = lo;
+    let mut step = T::one();
+    loop {
+        match last.checked_add(&step) {
+            Some(next) if pred(next) => {
+                last = next;
+                step = step.saturating_add(step);
+            }
+            Some(next) => return partition_point(last + T::one(), next, pred),
+            None if last == T::max_value() => return last,
+            None => return partition_point(last + T::one(), T::max_value(), pred),
+        }
+    }
+}
+
+/// Galloping search on a slice: returns the first index i such that pred(a[i]) is false,
+/// or a.len() if there is none. Faster than a binary search on the whole slice when the
+/// answer is close to the beginning.
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `pred`: the monotone predicate
+///
+/// returns: usize
+///
+/// \theta(\log answer)
+pub fn galloping_search<T, F>(a: &[T], mut pred: F) -> usize
+where
+    F: FnMut(&T) -> bool,
+{
+    exponential_search(0, |i| i < a.len() && pred(&a[i]))
+}
 
-    let mut ans = None;
+/// Stopping criterion for the bisection on real numbers.
+pub enum Precision {
+    /// Run exactly the given number of halvings.
+    Iterations(usize),
+    /// Stop when the range is smaller than the given value.
+    Epsilon(f64),
+}
 
-    while l < r {
-        let middle = l + (r - l) / 2;
+/// Bisection on real numbers: returns the point in [lo, hi] where pred switches from true
+/// to false, up to the given precision.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range
+/// * `precision`: when to stop halving the range
+/// * `pred`: the monotone predicate
+///
+/// returns: f64
+///
+/// \theta(\log ((hi - lo) / epsilon)) or \theta(iterations)
+pub fn bisect<F>(mut lo: f64, mut hi: f64, precision: Precision, mut pred: F) -> f64
+where
+    F: FnMut(f64) -> bool,
+{
+    assert!(lo <= hi, "Bisection: empty range");
 
-        if pred(a[middle]) {
-            l = middle + 1;
-            ans = Some(middle);
+    let mut step = |lo: &mut f64, hi: &mut f64| {
+        let middle = *lo + (*hi - *lo) / 2.;
+        if pred(middle) {
+            *lo = middle;
         } else {
-            r = middle;
+            *hi = middle;
+        }
+    };
+
+    match precision {
+        Precision::Iterations(iterations) => {
+            for _ in 0..iterations {
+                step(&mut lo, &mut hi);
+            }
+        }
+        Precision::Epsilon(epsilon) => {
+            assert!(epsilon > 0., "Bisection: epsilon must be positive");
+            while hi - lo > epsilon {
+                let width = hi - lo;
+                step(&mut lo, &mut hi);
+                // the range can not shrink anymore at this magnitude
+                if hi - lo >= width {
+                    break;
+                }
+            }
         }
     }
 
-    ans
+    lo + (hi - lo) / 2.
+}
+
+/// Runs binary search with the given predicate on the indices [l, r) of the array.
+/// Returns the last index whose item satisfies the predicate.
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `l`: the starting index of the binary search
+/// * `r`: the ending index of the binary search
+/// * `pred`: the predicate function to compare items
+///
+/// returns: Option<usize>
+///
+/// \theta(\log n)
+pub fn binary_search<T, F>(a: &[T], l: usize, r: usize, pred: F) -> Option<usize>
+where
+    F: Fn(&T) -> bool,
+{
+    assert!(r <= a.len(), "Binary Search: end out of range");
+
+    last_true(l, r, |i| pred(&a[i]))
 }
 
 /// Given an integer, returns it square root rounded down to the nearest integer.
@@ -44,13 +229,17 @@ where
 /// returns: sqrt(v)
 ///
 /// \theta(\log v)
-pub fn sqrt(v: i32) -> usize {
-    let a: Vec<i32> = (0..v).collect();
-    binary_search(a.clone(), 0, a.len(), |x| x * x <= v).unwrap()
+pub fn sqrt(v: u64) -> u64 {
+    if v == 0 {
+        return 0;
+    }
+
+    // x <= v / x is x * x <= v without overflowing, and sqrt(v) <= v / 2 for v >= 2
+    last_true(1, v / 2 + 2, |x| x <= v / x).unwrap()
 }
 
 /// Solves the social distancing problem: given an array of pairs of integers andn integer c, find
-/// the minimum distance d such that exactly c integers are placed within the intervals with their
+/// the maximum distance d such that c integers can be placed within the intervals with their
 /// minimum distance to be >= d.
 /// https://usaco.org/index.php?page=viewproblem2&cpid=1038
 ///
@@ -59,50 +248,127 @@ pub fn sqrt(v: i32) -> usize {
 /// * `intervals`: the array of intervals
 /// * `c`: the number of integers to be placed
 ///
-/// returns: (the minimum distance)
+/// returns: (the maximum distance)
 ///
 /// \theta (n \log l)
 pub fn social_distancing(mut intervals: Vec<(i32, i32)>, c: i32) -> usize {
     assert!(intervals.iter().all(|&(a, b)| a <= b));
 
-    let l = intervals.iter().fold(0, |mut sum, (a, b)| {
-        sum = sum + b - a + 1;
-        sum
-    });
-
     intervals.sort();
 
-    let predicate = |d| {
+    let first = intervals[0].0 as i64;
+    let last = intervals.iter().map(|&(_, b)| b).max().unwrap() as i64;
+
+    let predicate = |d: i64| {
         let mut total = 1;
 
-        let mut current = intervals[0].0;
-        for &interval in intervals.iter() {
-            while interval.0.max(current + d) <= interval.1 {
-                current = interval.0.max(current + d);
-                total = total + 1;
+        let mut current = first;
+        for &(a, b) in intervals.iter() {
+            while (a as i64).max(current + d) <= b as i64 {
+                current = (a as i64).max(current + d);
+                total += 1;
             }
         }
 
         total >= c
     };
 
-    binary_search((1..=l + 1).collect(), 0, l as usize, predicate).unwrap()
+    last_true(1, last - first + 2, predicate)
+        .expect("Social Distancing: the integers do not fit in the intervals") as usize
+}
+
+#[test]
+pub fn test_binary_search() {
+    let a = vec![1, 3, 3, 5, 8];
+    assert_eq!(binary_search(&a, 0, a.len(), |&x| x <= 3), Some(2));
+    assert_eq!(binary_search(&a, 0, a.len(), |&x| x < 1), None);
+    assert_eq!(binary_search(&a, 1, 3, |&x| x <= 10), Some(2));
+}
+
+#[test]
+pub fn test_partition_point() {
+    assert_eq!(partition_point(0, 10, |x| x < 4), 4);
+    assert_eq!(partition_point(0, 10, |_| true), 10);
+    assert_eq!(partition_point(0, 10, |_| false), 0);
+    assert_eq!(partition_point(-50_i32, 50, |x| x * 3 < -20), -6);
+    assert_eq!(first_true(0, 100, |x| x * x >= 50), Some(8));
+    assert_eq!(first_true(0, 5, |x| x > 10), None);
+    assert_eq!(last_true(0, 100, |x| x * x <= 50), Some(7));
+    assert_eq!(last_true(5, 100, |x| x < 5), None);
+}
+
+#[test]
+pub fn test_partition_point_near_overflow() {
+    let target = u64::MAX - 3;
+    assert_eq!(partition_point(0, u64::MAX, |x| x < target), target);
+    assert_eq!(partition_point(u64::MAX - 10, u64::MAX, |_| true), u64::MAX);
+
+    assert_eq!(partition_point(i64::MIN, i64::MAX, |x| x < -7), -7);
+    assert_eq!(
+        partition_point(i64::MIN, i64::MAX, |x| x < i64::MIN + 1),
+        i64::MIN + 1
+    );
+    assert_eq!(partition_point(i8::MIN, i8::MAX, |x| x < 100), 100);
+}
+
+#[test]
+pub fn test_exponential_search() {
+    assert_eq!(exponential_search(0, |x| x < 1000), 1000);
+    assert_eq!(exponential_search(-20_i64, |x| x * x * x < 27), 3);
+    assert_eq!(exponential_search(5, |x| x < 3), 5);
+    assert_eq!(exponential_search(0_u8, |_| true), u8::MAX);
+    assert_eq!(
+        exponential_search(0_u64, |x| x < u64::MAX - 1),
+        u64::MAX - 1
+    );
+
+    let a = vec![2, 4, 6, 8, 9, 11];
+    assert_eq!(galloping_search(&a, |&x| x % 2 == 0), 4);
+    assert_eq!(galloping_search(&a, |&x| x < 100), 6);
+    assert_eq!(galloping_search(&a, |&x| x > 100), 0);
+}
+
+#[test]
+pub fn test_bisect() {
+    let sqrt2 = bisect(0., 2., Precision::Epsilon(1e-9), |x| x * x <= 2.);
+    assert!((sqrt2 - 2_f64.sqrt()).abs() < 1e-9);
+
+    let cbrt = bisect(-10., 10., Precision::Iterations(100), |x| x * x * x <= -8.);
+    assert!((cbrt + 2.).abs() < 1e-12);
+
+    // epsilon below the representable precision must still terminate
+    let large = bisect(1e15, 1e16, Precision::Epsilon(1e-9), |x| x <= 3e15);
+    assert!((large - 3e15).abs() < 1.);
 }
 
 #[test]
 pub fn test_sqrt() {
-    let v = 4;
-    assert_eq!(sqrt(v), 2);
+    assert_eq!(sqrt(4), 2);
+    assert_eq!(sqrt(0), 0);
+    assert_eq!(sqrt(1), 1);
+    assert_eq!(sqrt(15), 3);
+    assert_eq!(sqrt(16), 4);
+    assert_eq!(sqrt(u64::MAX), u32::MAX as u64);
 }
 
 #[test]
 pub fn test_social_distancing() {
     let mut a = vec![(0, 3), (5, 7)];
     let mut c = 4;
-    assert_eq!(social_distancing(a, c), 1);
+    assert_eq!(social_distancing(a, c), 2);
 
     a = vec![(0, 3), (5, 7), (10, 13)];
     c = 3;
+    assert_eq!(social_distancing(a, c), 6);
+
+    a = vec![(0, 2), (4, 7), (9, 9)];
+    c = 5;
+    assert_eq!(social_distancing(a, c), 2);
 
-    assert_eq!(social_distancing(a, c), 5);
+    a = vec![
+        (-2_000_000_000, -1_999_999_990),
+        (2_000_000_000, 2_000_000_000),
+    ];
+    c = 2;
+    assert_eq!(social_distancing(a, c), 4_000_000_000);
 }
@@ -1,5 +1,6 @@
-mod binary_search_the_answer;
-mod prefix_sums;
+pub(crate) mod binary_search_the_answer;
+pub(crate) mod prefix_sums;
 mod sliding_window_maximum;
-pub(crate) mod sweep_line;
+mod sweep_line;
+mod ternary_search;
 mod two_pointers_trick;
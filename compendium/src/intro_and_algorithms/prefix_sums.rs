@@ -1,5 +1,315 @@
-use crate::intro_and_algorithms::sweep_line::prefix_sums;
-use std::usize;
+use std::ops::{Add, BitXor, Sub};
+
+/// Prefix sums of an array: sums[i] is the sum of the first i elements, so that the sum of
+/// any range [l, r) is sums[r] - sums[l]. All the ranges are 0-based and half-open.
+pub struct PrefixSums<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    sums: Vec<T>,
+}
+
+impl<T> PrefixSums<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// Computes the prefix sums of the array.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    ///
+    /// returns: PrefixSums<T>
+    /// \theta(n)
+    pub fn new(a: &[T]) -> Self {
+        let mut sums = Vec::with_capacity(a.len() + 1);
+        sums.push(T::default());
+        for (i, &x) in a.iter().enumerate() {
+            sums.push(sums[i] + x);
+        }
+        Self { sums }
+    }
+
+    /// Returns the number of elements of the array.
+    pub fn len(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the sum of the first i elements.
+    pub fn prefix(&self, i: usize) -> T {
+        self.sums[i]
+    }
+
+    /// Returns the sum of the elements in [l, r).
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range (included)
+    /// * `r`: the right endpoint of the range (excluded)
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn range_sum(&self, l: usize, r: usize) -> T {
+        assert!(l <= r, "Prefix Sums: invalid range");
+        self.sums[r] - self.sums[l]
+    }
+}
+
+/// Prefix XOR of an array: the XOR of any range [l, r) is prefix[r] ^ prefix[l], since every
+/// element is its own inverse.
+pub struct PrefixXor<T>
+where
+    T: Default + Copy + BitXor<Output = T>,
+{
+    prefix: Vec<T>,
+}
+
+impl<T> PrefixXor<T>
+where
+    T: Default + Copy + BitXor<Output = T>,
+{
+    /// Computes the prefix XOR of the array.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    ///
+    /// returns: PrefixXor<T>
+    /// \theta(n)
+    pub fn new(a: &[T]) -> Self {
+        let mut prefix = Vec::with_capacity(a.len() + 1);
+        prefix.push(T::default());
+        for (i, &x) in a.iter().enumerate() {
+            prefix.push(prefix[i] ^ x);
+        }
+        Self { prefix }
+    }
+
+    /// Returns the XOR of the elements in [l, r).
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range (included)
+    /// * `r`: the right endpoint of the range (excluded)
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn range_xor(&self, l: usize, r: usize) -> T {
+        assert!(l <= r, "Prefix Xor: invalid range");
+        self.prefix[r] ^ self.prefix[l]
+    }
+}
+
+/// Difference array: d[i] = a[i] - a[i - 1], so that adding v to the range [l, r) only
+/// changes d[l] and d[r]. The array is rebuilt with the prefix sums of d once all the
+/// additions are done.
+pub struct DifferenceArray<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    diff: Vec<T>,
+}
+
+impl<T> DifferenceArray<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates the difference array of n elements equal to zero.
+    pub fn with_len(n: usize) -> Self {
+        Self {
+            diff: vec![T::default(); n + 1],
+        }
+    }
+
+    /// Creates the difference array of the given array.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the initial values
+    ///
+    /// returns: DifferenceArray<T>
+    /// \theta(n)
+    pub fn from_slice(a: &[T]) -> Self {
+        let mut diff = Vec::with_capacity(a.len() + 1);
+        let mut previous = T::default();
+        for &x in a {
+            diff.push(x - previous);
+            previous = x;
+        }
+        diff.push(T::default());
+        Self { diff }
+    }
+
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds v to every element in [l, r).
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range (included)
+    /// * `r`: the right endpoint of the range (excluded)
+    /// * `v`: the value to add
+    ///
+    /// returns: ()
+    /// \theta(1)
+    pub fn add(&mut self, l: usize, r: usize, v: T) {
+        assert!(l <= r && r <= self.len(), "Difference Array: invalid range");
+        self.diff[l] = self.diff[l] + v;
+        self.diff[r] = self.diff[r] - v;
+    }
+
+    /// Returns the values of the array after all the additions.
+    ///
+    /// returns: Vec<T, Global>
+    /// \theta(n)
+    pub fn values(&self) -> Vec<T> {
+        let mut current = T::default();
+        self.diff[..self.len()]
+            .iter()
+            .map(|&d| {
+                current = current + d;
+                current
+            })
+            .collect()
+    }
+}
+
+/// 2D prefix sums: sums[i][j] is the sum of the rectangle [0, i) x [0, j), so that the sum of
+/// any rectangle is computed with inclusion-exclusion of four corners.
+pub struct PrefixSums2D<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    sums: Vec<Vec<T>>,
+}
+
+impl<T> PrefixSums2D<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// Computes the prefix sums of an n by m matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input matrix
+    ///
+    /// returns: PrefixSums2D<T>
+    /// \theta(n * m)
+    pub fn new(a: &[Vec<T>]) -> Self {
+        let n = a.len();
+        let m = a.first().map_or(0, |row| row.len());
+        let mut sums = vec![vec![T::default(); m + 1]; n + 1];
+
+        for i in 0..n {
+            assert_eq!(a[i].len(), m, "Prefix Sums 2D: rows of different length");
+            for j in 0..m {
+                sums[i + 1][j + 1] = a[i][j] + sums[i][j + 1] + sums[i + 1][j] - sums[i][j];
+            }
+        }
+
+        Self { sums }
+    }
+
+    /// Returns the sum of the rectangle of rows [r1, r2) and columns [c1, c2).
+    ///
+    /// # Arguments
+    ///
+    /// * `(r1, c1)`: the top left corner (included)
+    /// * `(r2, c2)`: the bottom right corner (excluded)
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn rectangle_sum(&self, (r1, c1): (usize, usize), (r2, c2): (usize, usize)) -> T {
+        assert!(r1 <= r2 && c1 <= c2, "Prefix Sums 2D: invalid rectangle");
+        self.sums[r2][c2] + self.sums[r1][c1] - self.sums[r1][c2] - self.sums[r2][c1]
+    }
+}
+
+/// 3D prefix sums: sums[i][j][k] is the sum of the box [0, i) x [0, j) x [0, k), so that the
+/// sum of any box is computed with inclusion-exclusion of eight corners.
+pub struct PrefixSums3D<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    sums: Vec<Vec<Vec<T>>>,
+}
+
+impl<T> PrefixSums3D<T>
+where
+    T: Default + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// Computes the prefix sums of an n by m by p tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input tensor
+    ///
+    /// returns: PrefixSums3D<T>
+    /// \theta(n * m * p)
+    pub fn new(a: &[Vec<Vec<T>>]) -> Self {
+        let n = a.len();
+        let m = a.first().map_or(0, |plane| plane.len());
+        let p = a
+            .first()
+            .and_then(|plane| plane.first())
+            .map_or(0, |row| row.len());
+        let mut s = vec![vec![vec![T::default(); p + 1]; m + 1]; n + 1];
+
+        for i in 0..n {
+            for j in 0..m {
+                assert_eq!(a[i][j].len(), p, "Prefix Sums 3D: rows of different length");
+                for k in 0..p {
+                    s[i + 1][j + 1][k + 1] = a[i][j][k]
+                        + s[i][j + 1][k + 1]
+                        + s[i + 1][j][k + 1]
+                        + s[i + 1][j + 1][k]
+                        + s[i][j][k]
+                        - s[i][j][k + 1]
+                        - s[i][j + 1][k]
+                        - s[i + 1][j][k];
+                }
+            }
+        }
+
+        Self { sums: s }
+    }
+
+    /// Returns the sum of the box [x1, x2) x [y1, y2) x [z1, z2).
+    ///
+    /// # Arguments
+    ///
+    /// * `(x1, y1, z1)`: the lower corner (included)
+    /// * `(x2, y2, z2)`: the upper corner (excluded)
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn box_sum(
+        &self,
+        (x1, y1, z1): (usize, usize, usize),
+        (x2, y2, z2): (usize, usize, usize),
+    ) -> T {
+        assert!(
+            x1 <= x2 && y1 <= y2 && z1 <= z2,
+            "Prefix Sums 3D: invalid box"
+        );
+        let s = &self.sums;
+        s[x2][y2][z2] + s[x1][y1][z2] + s[x1][y2][z1] + s[x2][y1][z1]
+            - s[x1][y2][z2]
+            - s[x2][y1][z2]
+            - s[x2][y2][z1]
+            - s[x1][y1][z1]
+    }
+}
 
 /// Given an array of n booleans and a list of m queries of the form (i, j), return a vector
 /// of m integers where the i-th integer is the number of pairs of consecutive elements in the
@@ -13,22 +323,14 @@ use std::usize;
 /// returns: Vec<i32, Global>
 /// /theta(n + m)
 pub fn ilya_and_queries(a: Vec<bool>, queries: Vec<(usize, usize)>) -> Vec<i32> {
-    let m = queries.len();
-    let n = a.len();
-    let mut result = Vec::with_capacity(m);
-
-    let mut ps = vec![0; n];
-    for i in 0..n - 1 {
-        ps[i] = i32::from(a[i] == a[i + 1]);
-    }
-
-    ps = prefix_sums(ps);
-
-    for (i, j) in queries {
-        result.push(ps[j - 1] - ps[i - 1])
-    }
+    let equal: Vec<i32> = a.windows(2).map(|w| i32::from(w[0] == w[1])).collect();
+    let ps = PrefixSums::new(&equal);
 
-    result
+    // queries are 1-based, the pairs (k, k + 1) with i <= k < j are the 0-based [i - 1, j - 1)
+    queries
+        .into_iter()
+        .map(|(i, j)| ps.range_sum(i - 1, j - 1))
+        .collect()
 }
 
 /// Given an array of n integers and a list of m range_sum(i, j) queries, permute the array
@@ -43,15 +345,13 @@ pub fn ilya_and_queries(a: Vec<bool>, queries: Vec<(usize, usize)>) -> Vec<i32>
 /// \theta(n log n + m)
 /// (not \theta(n log n + m log m) because we can use counting sort on the queries)
 pub fn little_girls_and_maximum(mut a: Vec<i32>, queries: Vec<(usize, usize)>) -> i32 {
-    let n = a.len();
-    let mut ps = vec![0; n + 1];
+    let mut frequencies = DifferenceArray::with_len(a.len());
 
     for (i, j) in queries {
-        ps[i - 1] += 1;
-        ps[j] -= 1;
+        frequencies.add(i - 1, j, 1);
     }
 
-    ps = prefix_sums(ps);
+    let mut ps = frequencies.values();
     ps.sort_by(|a, b| b.cmp(a));
     a.sort_by(|a, b| b.cmp(a));
 
@@ -87,3 +387,80 @@ pub fn test_little_girls_and_maximum() {
     queries = vec![(1, 5), (2, 3), (2, 3)];
     assert_eq!(little_girls_and_maximum(a, queries), 33);
 }
+
+#[test]
+pub fn test_prefix_sums() {
+    let a = vec![3, -1, 4, 1, -5, 9];
+    let ps = PrefixSums::new(&a);
+    assert_eq!(ps.len(), 6);
+    assert_eq!(ps.range_sum(0, 6), 11);
+    assert_eq!(ps.range_sum(1, 4), 4);
+    assert_eq!(ps.range_sum(2, 2), 0);
+    assert_eq!(ps.prefix(5), 2);
+
+    let empty: PrefixSums<i64> = PrefixSums::new(&[]);
+    assert!(empty.is_empty());
+    assert_eq!(empty.range_sum(0, 0), 0);
+}
+
+#[test]
+pub fn test_prefix_xor() {
+    let a = vec![5_u32, 1, 7, 2, 2];
+    let px = PrefixXor::new(&a);
+    assert_eq!(px.range_xor(0, 5), 5 ^ 1 ^ 7);
+    assert_eq!(px.range_xor(3, 5), 0);
+    assert_eq!(px.range_xor(1, 3), 1 ^ 7);
+}
+
+#[test]
+pub fn test_difference_array() {
+    let mut diff = DifferenceArray::from_slice(&[1, 2, 3, 4, 5]);
+    diff.add(0, 5, 10);
+    diff.add(1, 3, -2);
+    diff.add(4, 5, 1);
+    diff.add(2, 2, 100);
+    assert_eq!(diff.values(), vec![11, 10, 11, 14, 16]);
+
+    let mut zeros: DifferenceArray<i32> = DifferenceArray::with_len(4);
+    zeros.add(1, 4, 3);
+    assert_eq!(zeros.values(), vec![0, 3, 3, 3]);
+}
+
+#[test]
+pub fn test_prefix_sums_2d() {
+    let a = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+    let ps = PrefixSums2D::new(&a);
+    assert_eq!(ps.rectangle_sum((0, 0), (3, 3)), 45);
+    assert_eq!(ps.rectangle_sum((1, 1), (3, 3)), 28);
+    assert_eq!(ps.rectangle_sum((0, 1), (2, 2)), 7);
+    assert_eq!(ps.rectangle_sum((2, 0), (3, 3)), 24);
+    assert_eq!(ps.rectangle_sum((1, 1), (1, 3)), 0);
+}
+
+#[test]
+pub fn test_prefix_sums_3d() {
+    let (n, m, p) = (3, 4, 2);
+    let a: Vec<Vec<Vec<i64>>> = (0..n)
+        .map(|i| {
+            (0..m)
+                .map(|j| (0..p).map(|k| (i * 100 + j * 10 + k) as i64 - 50).collect())
+                .collect()
+        })
+        .collect();
+    let ps = PrefixSums3D::new(&a);
+
+    for (x1, y1, z1) in [(0, 0, 0), (1, 2, 1), (2, 0, 1)] {
+        for (x2, y2, z2) in [(3, 4, 2), (2, 3, 2), (3, 2, 1)] {
+            if x1 > x2 || y1 > y2 || z1 > z2 {
+                continue;
+            }
+            let mut expected = 0;
+            for plane in &a[x1..x2] {
+                for row in &plane[y1..y2] {
+                    expected += row[z1..z2].iter().sum::<i64>();
+                }
+            }
+            assert_eq!(ps.box_sum((x1, y1, z1), (x2, y2, z2)), expected);
+        }
+    }
+}
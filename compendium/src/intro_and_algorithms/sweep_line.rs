@@ -1,27 +1,79 @@
-use std::ops::Add;
+use crate::utilities::coordinate_compression::CoordinateCompressor;
+use std::ops::{Add, Mul, Sub};
 
-/// Returns an array containing the prefix sums of the input array.
+/// Kind of an event of the sweep. At the same position starts come before ends, so closed
+/// intervals touching in a single point are considered overlapping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EventKind {
+    Start,
+    End,
+}
+
+/// An event of the sweep: at position `x` the item `id` starts or ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Event<T> {
+    pub x: T,
+    pub kind: EventKind,
+    pub id: usize,
+}
+
+/// The point where the maximum number of intervals overlap, with the intervals containing it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MaximumOverlap<T> {
+    pub point: T,
+    pub count: usize,
+    pub intervals: Vec<usize>,
+}
+
+/// Sweep line engine: sorts the events by position (starts before ends) and hands them to
+/// the callback in order. Only comparisons are used, so coordinates can be negative or
+/// arbitrarily large.
 ///
 /// # Arguments
 ///
-/// * `a`: the input array
+/// * `events`: the events to process
+/// * `process`: the callback run on every event
 ///
-/// returns: Vec<T, Global>
+/// returns: ()
 ///
-/// \theta(n)
-pub fn prefix_sums<T>(a: Vec<T>) -> Vec<T>
+/// \theta(n log n)
+pub fn sweep<T, F>(mut events: Vec<Event<T>>, mut process: F)
 where
-    T: Ord + Clone + Default + Add<Output = T>,
+    T: Ord + Copy,
+    F: FnMut(&Event<T>),
 {
-    let n = a.len();
-    let mut prefix_sums = Vec::with_capacity(n);
-    prefix_sums.push(T::default());
-
-    for i in 0..n - 1 {
-        prefix_sums.push(prefix_sums[i].clone() + a[i].clone());
+    events.sort_by_key(|e| (e.x, e.kind));
+    for event in events.iter() {
+        process(event);
     }
+}
+
+/// Returns the start and end events of the closed intervals [a, b], the id of an event being
+/// the index of its interval.
+pub fn interval_events<T>(intervals: &[(T, T)]) -> Vec<Event<T>>
+where
+    T: Ord + Copy,
+{
+    assert!(intervals.iter().all(|&(a, b)| a <= b));
 
-    prefix_sums
+    intervals
+        .iter()
+        .enumerate()
+        .flat_map(|(id, &(a, b))| {
+            [
+                Event {
+                    x: a,
+                    kind: EventKind::Start,
+                    id,
+                },
+                Event {
+                    x: b,
+                    kind: EventKind::End,
+                    id,
+                },
+            ]
+        })
+        .collect()
 }
 
 /// Given an array of intervals of the form [a, b] with a <= b, return the maximum number
@@ -35,25 +87,288 @@ where
 ///
 /// \theta(n log n)
 pub fn maximum_number_overlapping_intervals(intervals: Vec<(i32, i32)>) -> i32 {
-    *overlapping_intervals(intervals).iter().max().unwrap()
+    maximum_overlap(&intervals).map_or(0, |overlap| overlap.count as i32)
+}
+
+/// Returns the leftmost point covered by the maximum number of closed intervals, together
+/// with the indices of the intervals covering it, or None if there are no intervals.
+///
+/// # Arguments
+///
+/// * `intervals`: the array intervals
+///
+/// returns: Option<MaximumOverlap<T>>
+///
+/// \theta(n log n)
+pub fn maximum_overlap<T>(intervals: &[(T, T)]) -> Option<MaximumOverlap<T>>
+where
+    T: Ord + Copy,
+{
+    let mut best: Option<(T, usize)> = None;
+    let mut active = 0;
+
+    // after a start event, every end at the same position is still to be processed
+    sweep(interval_events(intervals), |event| match event.kind {
+        EventKind::Start => {
+            active += 1;
+            if best.is_none_or(|(_, count)| active > count) {
+                best = Some((event.x, active));
+            }
+        }
+        EventKind::End => active -= 1,
+    });
+
+    best.map(|(point, count)| MaximumOverlap {
+        point,
+        count,
+        intervals: (0..intervals.len())
+            .filter(|&i| intervals[i].0 <= point && point <= intervals[i].1)
+            .collect(),
+    })
+}
+
+/// Returns, for every endpoint x of the intervals in increasing order, the number of closed
+/// intervals containing x. Between two consecutive endpoints the number is the same as in the
+/// left one, unless intervals end there.
+///
+/// # Arguments
+///
+/// * `intervals`: the array intervals
+///
+/// returns: Vec<(T, usize), Global>
+///
+/// \theta(n log n)
+pub fn overlapping_intervals<T>(intervals: &[(T, T)]) -> Vec<(T, usize)>
+where
+    T: Ord + Copy,
+{
+    let mut profile: Vec<(T, usize)> = Vec::new();
+    let mut active = 0;
+
+    sweep(interval_events(intervals), |event| {
+        if event.kind == EventKind::Start {
+            active += 1;
+        }
+        match profile.last_mut() {
+            Some(last) if last.0 == event.x => {
+                if event.kind == EventKind::Start {
+                    last.1 = active;
+                }
+            }
+            // the intervals ending in x still contain x
+            _ => profile.push((event.x, active)),
+        }
+        if event.kind == EventKind::End {
+            active -= 1;
+        }
+    });
+
+    profile
+}
+
+/// Returns the total length of the union of the intervals [a, b].
+///
+/// # Arguments
+///
+/// * `intervals`: the array intervals
+///
+/// returns: T
+///
+/// \theta(n log n)
+pub fn union_length<T>(intervals: &[(T, T)]) -> T
+where
+    T: Ord + Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    let mut length = T::default();
+    let mut active = 0;
+    let mut start = T::default();
+
+    sweep(interval_events(intervals), |event| match event.kind {
+        EventKind::Start => {
+            if active == 0 {
+                start = event.x;
+            }
+            active += 1;
+        }
+        EventKind::End => {
+            active -= 1;
+            if active == 0 {
+                length = length + (event.x - start);
+            }
+        }
+    });
+
+    length
+}
+
+/// Returns the area of the union of the rectangles (x1, y1, x2, y2) with x1 <= x2 and y1 <= y2.
+/// The sweep goes along the x axis, while a segment tree over the compressed y coordinates keeps
+/// the length of the y axis covered by at least one rectangle.
+///
+/// # Arguments
+///
+/// * `rectangles`: the array of rectangles
+///
+/// returns: T
+///
+/// \theta(n log n)
+pub fn union_area<T>(rectangles: &[(T, T, T, T)]) -> T
+where
+    T: Ord + Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    assert!(rectangles
+        .iter()
+        .all(|&(x1, y1, x2, y2)| x1 <= x2 && y1 <= y2));
+
+    let ys = CoordinateCompressor::new(rectangles.iter().flat_map(|&(_, y1, _, y2)| [y1, y2]));
+    let mut cover = CoverTree::new(ys.values().to_vec());
+
+    let x_ranges: Vec<(T, T)> = rectangles.iter().map(|&(x1, _, x2, _)| (x1, x2)).collect();
+
+    let mut area = T::default();
+    let mut previous: Option<T> = None;
+
+    sweep(interval_events(&x_ranges), |event| {
+        if let Some(x) = previous {
+            area = area + cover.covered() * (event.x - x);
+        }
+        previous = Some(event.x);
+
+        let (_, y1, _, y2) = rectangles[event.id];
+        let (l, r) = (ys.rank(&y1).unwrap(), ys.rank(&y2).unwrap());
+        match event.kind {
+            EventKind::Start => cover.update(l, r, 1),
+            EventKind::End => cover.update(l, r, -1),
+        }
+    });
+
+    area
 }
 
-pub fn overlapping_intervals(mut intervals: Vec<(i32, i32)>) -> Vec<i32> {
-    intervals.sort_by_key(|&x| x.0);
-    let max = intervals.iter().map(|&x| x.1).max().unwrap() as usize;
+/// Segment tree over the elementary segments [ys[i], ys[i + 1]), storing for every node how
+/// many rectangles cover its whole range and the covered length inside it. Since every removal
+/// matches a previous insertion on the same range, counts never need to be pushed down.
+struct CoverTree<T> {
+    ys: Vec<T>,
+    count: Vec<i32>,
+    covered: Vec<T>,
+}
 
-    let mut ps = vec![0; max + 2];
+impl<T> CoverTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn new(ys: Vec<T>) -> Self {
+        let size = 4 * ys.len().max(1);
+        Self {
+            ys,
+            count: vec![0; size],
+            covered: vec![T::default(); size],
+        }
+    }
 
-    for (a, b) in intervals {
-        ps[a as usize] += 1;
-        ps[b as usize + 1] -= 1;
+    fn covered(&self) -> T {
+        self.covered[0]
     }
 
-    prefix_sums(ps)
+    /// Adds delta to the cover count of the elementary segments [l, r).
+    fn update(&mut self, l: usize, r: usize, delta: i32) {
+        if l < r {
+            self.update_rec(l, r, delta, 0, self.ys.len() - 1, 0);
+        }
+    }
+
+    fn update_rec(&mut self, ql: usize, qr: usize, delta: i32, l: usize, r: usize, current: usize) {
+        if qr <= l || r <= ql {
+            return;
+        }
+
+        if ql <= l && r <= qr {
+            self.count[current] += delta;
+        } else {
+            let mid = (l + r) / 2;
+            self.update_rec(ql, qr, delta, l, mid, 2 * current + 1);
+            self.update_rec(ql, qr, delta, mid, r, 2 * current + 2);
+        }
+
+        self.covered[current] = if self.count[current] > 0 {
+            self.ys[r] - self.ys[l]
+        } else if r - l == 1 {
+            T::default()
+        } else {
+            self.covered[2 * current + 1] + self.covered[2 * current + 2]
+        };
+    }
 }
 
 #[test]
 pub fn test_maximum_number_overlapping_intervals() {
     let a = vec![(1, 3), (2, 4), (3, 6), (7, 10)];
     assert_eq!(maximum_number_overlapping_intervals(a), 3);
+    assert_eq!(maximum_number_overlapping_intervals(vec![]), 0);
+}
+
+#[test]
+pub fn test_maximum_overlap() {
+    let a = vec![(-10, -5), (-7, 2), (-5, 0), (1, 3)];
+    assert_eq!(
+        maximum_overlap(&a),
+        Some(MaximumOverlap {
+            point: -5,
+            count: 3,
+            intervals: vec![0, 1, 2],
+        })
+    );
+
+    let huge = vec![(i64::MIN, 0), (-1, i64::MAX), (1 << 60, i64::MAX)];
+    let overlap = maximum_overlap(&huge).unwrap();
+    assert_eq!((overlap.point, overlap.count), (-1, 2));
+    assert_eq!(overlap.intervals, vec![0, 1]);
+
+    assert_eq!(maximum_overlap::<i32>(&[]), None);
+}
+
+#[test]
+pub fn test_overlapping_intervals() {
+    let a = vec![(1, 3), (2, 4), (3, 6), (7, 10)];
+    assert_eq!(
+        overlapping_intervals(&a),
+        vec![(1, 1), (2, 2), (3, 3), (4, 2), (6, 1), (7, 1), (10, 1)]
+    );
+}
+
+#[test]
+pub fn test_union_length() {
+    let a = vec![(1, 3), (2, 4), (6, 7), (-5, -2), (-3, -3)];
+    assert_eq!(union_length(&a), 3 + 1 + 3);
+    let b = vec![(-1_000_000_000_000_i64, 1_000_000_000_000)];
+    assert_eq!(union_length(&b), 2_000_000_000_000);
+}
+
+#[test]
+pub fn test_union_area() {
+    let rectangles = vec![(0, 0, 2, 2), (1, 1, 3, 3), (-2, -2, -1, 5), (0, 0, 1, 1)];
+
+    // brute force over the unit cells
+    let mut expected = 0;
+    for x in -3..4 {
+        for y in -3..6 {
+            let inside = rectangles
+                .iter()
+                .any(|&(x1, y1, x2, y2)| x1 <= x && x < x2 && y1 <= y && y < y2);
+            expected += i64::from(inside);
+        }
+    }
+    assert_eq!(union_area(&rectangles), expected);
+    assert_eq!(expected, 7 + 7);
+
+    let large = vec![
+        (-1_000_000_i64, -1_000_000, 1_000_000, 1_000_000),
+        (0, 0, 2_000_000, 2_000_000),
+    ];
+    assert_eq!(
+        union_area(&large),
+        2 * 4_000_000_000_000 - 1_000_000_000_000
+    );
+    assert_eq!(union_area::<i64>(&[]), 0);
 }
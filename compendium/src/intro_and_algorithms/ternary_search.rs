@@ -0,0 +1,293 @@
+use crate::intro_and_algorithms::binary_search_the_answer::{partition_point, Precision};
+use num_traits::PrimInt;
+
+/// Ternary search on integers: returns a point of [lo, hi] where the unimodal function f is
+/// minimum. The function must be strictly decreasing and then strictly increasing, or convex.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range (included)
+/// * `f`: the unimodal function
+///
+/// returns: T
+///
+/// \theta(\log (hi - lo)) evaluations of f
+pub fn ternary_search_min<T, V, F>(lo: T, hi: T, f: F) -> T
+where
+    T: PrimInt,
+    V: PartialOrd,
+    F: FnMut(T) -> V,
+{
+    ternary_search_by(lo, hi, f, |a, b| a < b)
+}
+
+/// Ternary search on integers: returns a point of [lo, hi] where the unimodal function f is
+/// maximum. The function must be strictly increasing and then strictly decreasing, or concave.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range (included)
+/// * `f`: the unimodal function
+///
+/// returns: T
+///
+/// \theta(\log (hi - lo)) evaluations of f
+pub fn ternary_search_max<T, V, F>(lo: T, hi: T, f: F) -> T
+where
+    T: PrimInt,
+    V: PartialOrd,
+    F: FnMut(T) -> V,
+{
+    ternary_search_by(lo, hi, f, |a, b| a > b)
+}
+
+/// Shrinks [lo, hi] by a third at every step, discarding the side of the worst probe,
+/// then checks the last (at most three) candidates one by one.
+fn ternary_search_by<T, V, F, B>(mut lo: T, mut hi: T, mut f: F, better: B) -> T
+where
+    T: PrimInt,
+    F: FnMut(T) -> V,
+    B: Fn(&V, &V) -> bool,
+{
+    assert!(lo <= hi, "Ternary Search: empty range");
+
+    let three = T::one() + T::one() + T::one();
+    while hi - lo >= three {
+        let third = (hi - lo) / three;
+        let m1 = lo + third;
+        let m2 = hi - third;
+        if better(&f(m1), &f(m2)) {
+            hi = m2 - T::one();
+        } else {
+            lo = m1 + T::one();
+        }
+    }
+
+    let mut best = lo;
+    let mut best_value = f(lo);
+    let mut x = lo;
+    while x < hi {
+        x = x + T::one();
+        let value = f(x);
+        if better(&value, &best_value) {
+            best = x;
+            best_value = value;
+        }
+    }
+
+    best
+}
+
+/// Binary search on the slope: returns the first point of [lo, hi] where the convex function f
+/// is minimum, i.e. the first x such that f(x) <= f(x + 1). Compared to the ternary search it
+/// needs two evaluations per halving instead of two per third, and handles plateaus.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range (included)
+/// * `f`: the convex function
+///
+/// returns: T
+///
+/// \theta(\log (hi - lo)) evaluations of f
+pub fn convex_minimum<T, V, F>(lo: T, hi: T, mut f: F) -> T
+where
+    T: PrimInt,
+    V: PartialOrd,
+    F: FnMut(T) -> V,
+{
+    assert!(lo <= hi, "Convex Minimum: empty range");
+
+    partition_point(lo, hi, |x| f(x) > f(x + T::one()))
+}
+
+/// Ternary search on real numbers: returns the point of [lo, hi] where the unimodal function f
+/// is minimum, up to the given precision.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range
+/// * `precision`: when to stop shrinking the range
+/// * `f`: the unimodal function
+///
+/// returns: f64
+///
+/// \theta(\log_{3/2} ((hi - lo) / epsilon)) or \theta(iterations)
+pub fn ternary_search_min_real<F>(lo: f64, hi: f64, precision: Precision, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    ternary_search_real_by(lo, hi, precision, |x| -f(x))
+}
+
+/// Ternary search on real numbers: returns the point of [lo, hi] where the unimodal function f
+/// is maximum, up to the given precision.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range
+/// * `precision`: when to stop shrinking the range
+/// * `f`: the unimodal function
+///
+/// returns: f64
+///
+/// \theta(\log_{3/2} ((hi - lo) / epsilon)) or \theta(iterations)
+pub fn ternary_search_max_real<F>(lo: f64, hi: f64, precision: Precision, f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    ternary_search_real_by(lo, hi, precision, f)
+}
+
+fn ternary_search_real_by<F>(mut lo: f64, mut hi: f64, precision: Precision, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    assert!(lo <= hi, "Ternary Search: empty range");
+
+    let mut step = |lo: &mut f64, hi: &mut f64| {
+        let third = (*hi - *lo) / 3.;
+        let m1 = *lo + third;
+        let m2 = *hi - third;
+        if f(m1) < f(m2) {
+            *lo = m1;
+        } else {
+            *hi = m2;
+        }
+    };
+
+    match precision {
+        Precision::Iterations(iterations) => {
+            for _ in 0..iterations {
+                step(&mut lo, &mut hi);
+            }
+        }
+        Precision::Epsilon(epsilon) => {
+            assert!(epsilon > 0., "Ternary Search: epsilon must be positive");
+            while hi - lo > epsilon {
+                let width = hi - lo;
+                step(&mut lo, &mut hi);
+                if hi - lo >= width {
+                    break;
+                }
+            }
+        }
+    }
+
+    lo + (hi - lo) / 2.
+}
+
+/// Golden-section search: returns the point of [lo, hi] where the unimodal function f is
+/// minimum, up to the given precision. The probes split the range by the golden ratio, so one
+/// of them is reused at the next step and every iteration costs a single evaluation of f.
+///
+/// # Arguments
+///
+/// * `lo`: the start of the range
+/// * `hi`: the end of the range
+/// * `precision`: when to stop shrinking the range
+/// * `f`: the unimodal function
+///
+/// returns: f64
+///
+/// \theta(\log_{\phi} ((hi - lo) / epsilon)) or \theta(iterations)
+pub fn golden_section_search<F>(mut lo: f64, mut hi: f64, precision: Precision, mut f: F) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    assert!(lo <= hi, "Golden Section Search: empty range");
+
+    // 1 / phi
+    let ratio = (5_f64.sqrt() - 1.) / 2.;
+
+    let mut m1 = hi - ratio * (hi - lo);
+    let mut m2 = lo + ratio * (hi - lo);
+    let mut f1 = f(m1);
+    let mut f2 = f(m2);
+
+    let mut iteration = 0;
+    loop {
+        let done = match precision {
+            Precision::Iterations(iterations) => iteration >= iterations,
+            Precision::Epsilon(epsilon) => hi - lo <= epsilon,
+        };
+        if done || m1 >= m2 {
+            break;
+        }
+        iteration += 1;
+
+        if f1 < f2 {
+            hi = m2;
+            m2 = m1;
+            f2 = f1;
+            m1 = hi - ratio * (hi - lo);
+            f1 = f(m1);
+        } else {
+            lo = m1;
+            m1 = m2;
+            f1 = f2;
+            m2 = lo + ratio * (hi - lo);
+            f2 = f(m2);
+        }
+    }
+
+    lo + (hi - lo) / 2.
+}
+
+#[test]
+pub fn test_ternary_search_integers() {
+    let f = |x: i64| (x - 17) * (x - 17) + 3;
+    assert_eq!(ternary_search_min(-1000, 1000, f), 17);
+    assert_eq!(ternary_search_min(20, 1000, f), 20);
+    assert_eq!(ternary_search_min(5, 5, f), 5);
+
+    let g = |x: i32| -(x - 4).abs();
+    assert_eq!(ternary_search_max(0, 10, g), 4);
+    assert_eq!(ternary_search_max(0_u32, 4, |x| x * (4 - x)), 2);
+}
+
+#[test]
+pub fn test_convex_minimum() {
+    // cost of placing a meeting point at x: sum of weighted distances, convex with plateaus
+    let houses = [(1, 2), (4, 1), (9, 3), (12, 1)];
+    let cost = |x: i64| houses.iter().map(|&(p, w)| w * (x - p).abs()).sum::<i64>();
+
+    let best = convex_minimum(-100, 100, cost);
+    let brute = (-100..=100).min_by_key(|&x| (cost(x), x)).unwrap();
+    assert_eq!(best, brute);
+    assert_eq!(cost(ternary_search_min(-100, 100, cost)), cost(brute));
+
+    assert_eq!(convex_minimum(0_u64, 10, |x| 10 - x), 10);
+    assert_eq!(convex_minimum(0_u64, 10, |x| x), 0);
+}
+
+#[test]
+pub fn test_ternary_search_reals() {
+    // minimize the total distance from a point on the x axis to the given points
+    let points = [(0., 1.), (3., 2.), (4., 0.5)];
+    let distance = |x: f64| {
+        points
+            .iter()
+            .map(|(px, py): &(f64, f64)| ((x - px).powi(2) + py.powi(2)).sqrt())
+            .sum::<f64>()
+    };
+
+    let ternary = ternary_search_min_real(-10., 10., Precision::Epsilon(1e-9), distance);
+    let golden = golden_section_search(-10., 10., Precision::Epsilon(1e-9), distance);
+    assert!((ternary - golden).abs() < 1e-6);
+    assert!(distance(ternary) <= distance(ternary + 1e-3));
+    assert!(distance(ternary) <= distance(ternary - 1e-3));
+
+    let peak = ternary_search_max_real(0., 3., Precision::Iterations(200), |x| x.sin());
+    assert!((peak - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+
+    let parabola = golden_section_search(-5., 5., Precision::Iterations(100), |x| {
+        (x - 1.5) * (x - 1.5)
+    });
+    assert!((parabola - 1.5).abs() < 1e-6);
+}
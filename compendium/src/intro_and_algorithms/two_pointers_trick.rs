@@ -1,6 +1,221 @@
 use core::hash::Hash;
 use std::collections::HashMap;
-use std::fmt::Display;
+
+/// Incremental state of a window a[i..=j] for the two pointers trick: the driver only moves
+/// the pointers forward, adding a[j] when j advances and removing a[i] when i advances.
+pub trait WindowState<T> {
+    fn add(&mut self, x: &T);
+    fn remove(&mut self, x: &T);
+    fn is_valid(&self) -> bool;
+}
+
+/// Returns the pointers to the longest valid window, the leftmost one in case of ties.
+/// Validity must be closed under taking sub-windows (e.g. "at most k distinct values").
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `state`: the state of the empty window
+///
+/// returns: Option<(usize, usize)>
+///
+/// \theta(n) calls to the state
+pub fn longest_valid_window<T, S>(a: &[T], state: S) -> Option<(usize, usize)>
+where
+    S: WindowState<T>,
+{
+    let mut best: Option<(usize, usize)> = None;
+    for_each_minimal_start(a, state, |i, j| {
+        if best.is_none_or(|(bi, bj)| j - i > bj - bi) {
+            best = Some((i, j));
+        }
+    });
+    best
+}
+
+/// Counts the valid subarrays. Validity must be closed under taking sub-windows: for every j,
+/// all the windows [i', j] with i' >= i are valid, where i is the smallest valid start.
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `state`: the state of the empty window
+///
+/// returns: u64
+///
+/// \theta(n) calls to the state
+pub fn count_valid_subarrays<T, S>(a: &[T], state: S) -> u64
+where
+    S: WindowState<T>,
+{
+    let mut count = 0;
+    for_each_minimal_start(a, state, |i, j| count += (j - i + 1) as u64);
+    count
+}
+
+/// Returns all the maximal valid windows, i.e. valid windows that can not be extended neither
+/// to the left nor to the right, sorted by their left pointer.
+/// Validity must be closed under taking sub-windows.
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `state`: the state of the empty window
+///
+/// returns: Vec<(usize, usize), Global>
+///
+/// \theta(n) calls to the state
+pub fn maximal_windows<T, S>(a: &[T], state: S) -> Vec<(usize, usize)>
+where
+    S: WindowState<T>,
+{
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for_each_minimal_start(a, state, |i, j| {
+        // [i, j - 1] is not maximal if it can be extended to [i, j]
+        if let Some(last) = windows.last_mut() {
+            if last.0 == i && last.1 + 1 == j {
+                last.1 = j;
+                return;
+            }
+        }
+        windows.push((i, j));
+    });
+    windows
+}
+
+/// Returns the pointers to the shortest valid window, the leftmost one in case of ties.
+/// Validity must be closed under extending the window (e.g. "sum at least s").
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `state`: the state of the empty window
+///
+/// returns: Option<(usize, usize)>
+///
+/// \theta(n) calls to the state
+pub fn shortest_valid_window<T, S>(a: &[T], mut state: S) -> Option<(usize, usize)>
+where
+    S: WindowState<T>,
+{
+    let mut best: Option<(usize, usize)> = None;
+    let mut i = 0;
+
+    for j in 0..a.len() {
+        state.add(&a[j]);
+        while i <= j && state.is_valid() {
+            if best.is_none_or(|(bi, bj)| j - i < bj - bi) {
+                best = Some((i, j));
+            }
+            state.remove(&a[i]);
+            i += 1;
+        }
+    }
+
+    best
+}
+
+/// Calls f(i, j) for every j such that some window ending in j is valid, where i is the
+/// smallest valid start for j. The sequence of i is non-decreasing.
+fn for_each_minimal_start<T, S, F>(a: &[T], mut state: S, mut f: F)
+where
+    S: WindowState<T>,
+    F: FnMut(usize, usize),
+{
+    let mut i = 0;
+
+    for j in 0..a.len() {
+        state.add(&a[j]);
+        while i <= j && !state.is_valid() {
+            state.remove(&a[i]);
+            i += 1;
+        }
+        if i <= j {
+            f(i, j);
+        }
+    }
+}
+
+/// Window state for "at most k distinct values", with the occurrences kept in a HashMap.
+pub struct AtMostKDistinct<T>
+where
+    T: Eq + Hash + Clone,
+{
+    k: usize,
+    support: HashMap<T, usize>,
+}
+
+impl<T> AtMostKDistinct<T>
+where
+    T: Eq + Hash + Clone,
+{
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            support: HashMap::new(),
+        }
+    }
+}
+
+impl<T> WindowState<T> for AtMostKDistinct<T>
+where
+    T: Eq + Hash + Clone,
+{
+    fn add(&mut self, x: &T) {
+        *self.support.entry(x.clone()).or_insert(0) += 1;
+    }
+
+    fn remove(&mut self, x: &T) {
+        if let Some(count) = self.support.get_mut(x) {
+            *count -= 1;
+            if *count == 0 {
+                self.support.remove(x);
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.support.len() <= self.k
+    }
+}
+
+/// Window state for "at most k distinct values" when the values are small integers in
+/// [0, max_value]: the occurrences are kept in a dense array instead of a HashMap.
+pub struct AtMostKDistinctDense {
+    k: usize,
+    distinct: usize,
+    support: Vec<usize>,
+}
+
+impl AtMostKDistinctDense {
+    pub fn new(k: usize, max_value: usize) -> Self {
+        Self {
+            k,
+            distinct: 0,
+            support: vec![0; max_value + 1],
+        }
+    }
+}
+
+impl WindowState<usize> for AtMostKDistinctDense {
+    fn add(&mut self, &x: &usize) {
+        if self.support[x] == 0 {
+            self.distinct += 1;
+        }
+        self.support[x] += 1;
+    }
+
+    fn remove(&mut self, &x: &usize) {
+        self.support[x] -= 1;
+        if self.support[x] == 0 {
+            self.distinct -= 1;
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.distinct <= self.k
+    }
+}
 
 /// Returns the pointers to the longest k-good segment of the input array.
 /// A subarray is called k-good if it contains no more than k different values.
@@ -14,40 +229,28 @@ use std::fmt::Display;
 ///
 /// returns: (usize, usize)
 ///
-/// \theta(n log n) due to std::collections::HashMap
+/// \theta(n) expected, due to std::collections::HashMap
 pub fn longest_k_good_segments<T>(a: Vec<T>, k: usize) -> (usize, usize)
 where
-    T: Ord + Copy + Default + Hash + Display,
+    T: Eq + Hash + Clone,
 {
-    let n = a.len();
-    let mut i = 0;
-    let mut j = 0;
-    let mut best_i = 0;
-    let mut best_j = 0;
-    let mut best_count = 0;
-
-    let mut support: HashMap<T, i32> = HashMap::with_capacity(n);
-
-    while j < n {
-        *support.entry(a[j]).or_insert(0) += 1;
-        while support.len() > k {
-            if let Some(count) = support.get_mut(&a[i]) {
-                *count -= 1;
-                if *count == 0 {
-                    support.remove(&a[i]);
-                }
-            }
-            i = i + 1;
-        }
-        if j - i + 1 > best_count {
-            best_count = j - i + 1;
-            best_i = i;
-            best_j = j;
-        }
-        j = j + 1
-    }
+    longest_valid_window(&a, AtMostKDistinct::new(k)).unwrap_or((0, 0))
+}
 
-    (best_i, best_j)
+/// Same as `longest_k_good_segments` for values in [0, max_value], counting the occurrences
+/// in a dense array.
+///
+/// # Arguments
+///
+/// * `a`: the input array
+/// * `k`: the value k
+/// * `max_value`: the largest value in the array
+///
+/// returns: (usize, usize)
+///
+/// \theta(n + max_value)
+pub fn longest_k_good_segments_dense(a: &[usize], k: usize, max_value: usize) -> (usize, usize) {
+    longest_valid_window(a, AtMostKDistinctDense::new(k, max_value)).unwrap_or((0, 0))
 }
 
 /// Given an array of n elements, count the number of ways of splitting it in three parts
@@ -110,3 +313,72 @@ pub fn test_numbers_of_ways() {
     a = vec![1, 2, 3, 4];
     assert_eq!(numbers_of_ways(a), 0);
 }
+
+#[test]
+pub fn test_k_good_dense() {
+    let a = vec![1, 1, 2, 3, 3, 4, 4, 4, 1, 3, 2, 3];
+    assert_eq!(longest_k_good_segments_dense(&a, 3, 4), (3, 9));
+    assert_eq!(longest_k_good_segments_dense(&a, 1, 4), (5, 7));
+    assert_eq!(longest_k_good_segments(a, 1), (5, 7));
+}
+
+#[test]
+pub fn test_count_valid_subarrays() {
+    let a = vec![1, 2, 1, 2, 3];
+    // brute force over all the subarrays
+    for k in 0..=3 {
+        let mut expected = 0;
+        for i in 0..a.len() {
+            for j in i..a.len() {
+                let mut distinct = a[i..=j].to_vec();
+                distinct.sort();
+                distinct.dedup();
+                expected += u64::from(distinct.len() <= k);
+            }
+        }
+        assert_eq!(count_valid_subarrays(&a, AtMostKDistinct::new(k)), expected);
+    }
+}
+
+#[test]
+pub fn test_maximal_windows() {
+    let a = vec![1, 2, 1, 3, 3, 1, 4];
+    assert_eq!(
+        maximal_windows(&a, AtMostKDistinct::new(2)),
+        vec![(0, 2), (2, 5), (5, 6)]
+    );
+    assert_eq!(
+        maximal_windows(&a, AtMostKDistinct::new(1)),
+        vec![(0, 0), (1, 1), (2, 2), (3, 4), (5, 5), (6, 6)]
+    );
+    assert!(maximal_windows(&a, AtMostKDistinct::new(0)).is_empty());
+}
+
+#[test]
+pub fn test_shortest_valid_window() {
+    struct SumAtLeast {
+        target: i32,
+        sum: i32,
+    }
+
+    impl WindowState<i32> for SumAtLeast {
+        fn add(&mut self, x: &i32) {
+            self.sum += x;
+        }
+
+        fn remove(&mut self, x: &i32) {
+            self.sum -= x;
+        }
+
+        fn is_valid(&self) -> bool {
+            self.sum >= self.target
+        }
+    }
+
+    let a = vec![2, 3, 1, 2, 4, 3];
+    let state = |target| SumAtLeast { target, sum: 0 };
+    assert_eq!(shortest_valid_window(&a, state(7)), Some((4, 5)));
+    assert_eq!(shortest_valid_window(&a, state(4)), Some((4, 4)));
+    assert_eq!(shortest_valid_window(&a, state(15)), Some((0, 5)));
+    assert_eq!(shortest_valid_window(&a, state(16)), None);
+}
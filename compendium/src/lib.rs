@@ -1,7 +1,8 @@
 mod bst;
-mod dynamic_programming;
+pub mod dynamic_programming;
 mod fenwick_trees;
 mod intro_and_algorithms;
 mod mo_algorithm;
 mod segment_trees;
+mod sqrt_decomposition;
 mod utilities;
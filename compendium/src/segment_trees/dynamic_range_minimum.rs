@@ -1,5 +1,5 @@
+use crate::segment_trees::operation::Min;
 use crate::segment_trees::segment_tree::SegmentTree;
-use std::cmp::min;
 use std::fmt::Debug;
 use std::ops::{Add, Mul};
 
@@ -18,7 +18,7 @@ where
 {
     pub fn from_vec(v: Vec<T>) -> Self {
         Self {
-            segment_tree: SegmentTree::from_vec(v, min, min, |a, b| a + b),
+            segment_tree: SegmentTree::with_operation::<Min>(v, |a, b| a + b),
         }
     }
 
@@ -1,2 +1,4 @@
 mod dynamic_range_minimum;
-mod segment_tree;
+pub(crate) mod operation;
+pub(crate) mod segment_tree;
+mod sparse_table;
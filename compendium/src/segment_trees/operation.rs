@@ -0,0 +1,86 @@
+use num_traits::PrimInt;
+use std::ops::{Add, BitAnd, BitOr, BitXor};
+
+/// An associative binary operation used to aggregate ranges of values, shared by the range
+/// query data structures (Segment Trees, Sparse Tables).
+pub trait Operation<T> {
+    fn combine(a: T, b: T) -> T;
+}
+
+/// Marker for operations such that combine(a, a) = a. Overlapping ranges can be combined
+/// without changing the result, which allows O(1) queries on Sparse Tables.
+pub trait Idempotent<T>: Operation<T> {}
+
+pub struct Min;
+pub struct Max;
+pub struct Sum;
+pub struct Gcd;
+pub struct And;
+pub struct Or;
+pub struct Xor;
+
+impl<T: Ord> Operation<T> for Min {
+    fn combine(a: T, b: T) -> T {
+        a.min(b)
+    }
+}
+
+impl<T: Ord> Operation<T> for Max {
+    fn combine(a: T, b: T) -> T {
+        a.max(b)
+    }
+}
+
+impl<T: Add<Output = T>> Operation<T> for Sum {
+    fn combine(a: T, b: T) -> T {
+        a + b
+    }
+}
+
+/// Greatest common divisor with the Euclidean algorithm, defined for non-negative integers.
+impl<T: PrimInt> Operation<T> for Gcd {
+    fn combine(mut a: T, mut b: T) -> T {
+        while b != T::zero() {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+}
+
+impl<T: BitAnd<Output = T>> Operation<T> for And {
+    fn combine(a: T, b: T) -> T {
+        a & b
+    }
+}
+
+impl<T: BitOr<Output = T>> Operation<T> for Or {
+    fn combine(a: T, b: T) -> T {
+        a | b
+    }
+}
+
+impl<T: BitXor<Output = T>> Operation<T> for Xor {
+    fn combine(a: T, b: T) -> T {
+        a ^ b
+    }
+}
+
+impl<T: Ord> Idempotent<T> for Min {}
+impl<T: Ord> Idempotent<T> for Max {}
+impl<T: PrimInt> Idempotent<T> for Gcd {}
+impl<T: BitAnd<Output = T>> Idempotent<T> for And {}
+impl<T: BitOr<Output = T>> Idempotent<T> for Or {}
+
+#[test]
+pub fn test_operations() {
+    assert_eq!(<Min as Operation<i32>>::combine(3, -2), -2);
+    assert_eq!(<Max as Operation<i32>>::combine(3, -2), 3);
+    assert_eq!(<Sum as Operation<i32>>::combine(3, -2), 1);
+    assert_eq!(<Gcd as Operation<u64>>::combine(12, 18), 6);
+    assert_eq!(<Gcd as Operation<u64>>::combine(0, 7), 7);
+    assert_eq!(<And as Operation<u8>>::combine(0b1100, 0b1010), 0b1000);
+    assert_eq!(<Or as Operation<u8>>::combine(0b1100, 0b1010), 0b1110);
+    assert_eq!(<Xor as Operation<u8>>::combine(0b1100, 0b1010), 0b0110);
+}
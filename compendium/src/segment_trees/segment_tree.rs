@@ -1,3 +1,4 @@
+use crate::segment_trees::operation::Operation;
 use std::fmt::Debug;
 use std::ops::Mul;
 
@@ -223,6 +224,27 @@ where
     }
 }
 
+impl<T> SegmentTree<T, fn(T, T) -> T>
+where
+    T: Default + Copy + Clone + Debug + Ord + From<usize> + Mul<T, Output = T>,
+{
+    /// Builds a segment tree whose queries aggregate ranges with the operation `O`.
+    ///
+    /// # Arguments
+    ///
+    /// * `v`: the input vector
+    /// * `update`: how an update value is applied to a node
+    ///
+    /// returns: SegmentTree<T, fn(T, T) -> T>
+    /// \theta(n)
+    pub fn with_operation<O>(v: Vec<T>, update: fn(T, T) -> T) -> Self
+    where
+        O: Operation<T>,
+    {
+        Self::from_vec(v, O::combine, O::combine, update)
+    }
+}
+
 #[test]
 fn test_segment_tree() {
     let v = vec![1, 2, 3, 4];
@@ -241,3 +263,14 @@ fn test_segment_tree_range_update() {
     assert_eq!(segment_tree.query(0, 3), Some(10));
     assert_eq!(segment_tree.query(0, 0), Some(3));
 }
+
+#[test]
+fn test_segment_tree_with_operation() {
+    use crate::segment_trees::operation::Max;
+
+    let v = vec![1, 5, 2, 4];
+    let mut segment_tree = SegmentTree::with_operation::<Max>(v, |a, b| a + b);
+    assert_eq!(segment_tree.query(0, 3), Some(5));
+    segment_tree.update(2, 4);
+    assert_eq!(segment_tree.query(2, 3), Some(6));
+}
@@ -0,0 +1,307 @@
+use crate::segment_trees::operation::{Idempotent, Operation};
+use std::marker::PhantomData;
+
+/// Sparse Table over a static array: table[k][i] aggregates the 2^k elements starting at i.
+/// Queries are on closed ranges [l, r], as in the Segment Tree.
+pub struct SparseTable<T, O>
+where
+    T: Clone,
+    O: Operation<T>,
+{
+    table: Vec<Vec<T>>,
+    operation: PhantomData<O>,
+}
+
+impl<T, O> SparseTable<T, O>
+where
+    T: Clone,
+    O: Operation<T>,
+{
+    /// Builds the Sparse Table of the given array.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    ///
+    /// returns: SparseTable<T, O>
+    /// \theta(n log n)
+    pub fn from_vec(a: Vec<T>) -> Self {
+        let n = a.len();
+        let mut table = vec![a];
+
+        let mut k = 1;
+        while (1 << k) <= n {
+            let half = 1 << (k - 1);
+            let previous: &Vec<T> = &table[k - 1];
+            let level = (0..=n - (1 << k))
+                .map(|i| O::combine(previous[i].clone(), previous[i + half].clone()))
+                .collect();
+            table.push(level);
+            k += 1;
+        }
+
+        Self {
+            table,
+            operation: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table[0].is_empty()
+    }
+
+    /// Aggregates the range [l, r] splitting it in disjoint blocks of decreasing power of two
+    /// sizes. Works for every associative operation, the order of the elements is preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range
+    /// * `r`: the right endpoint of the range
+    ///
+    /// returns: T
+    /// \theta(log n)
+    pub fn query(&self, l: usize, r: usize) -> T {
+        assert!(
+            l <= r && r < self.len(),
+            "Sparse Table: range out of bounds"
+        );
+
+        let mut result: Option<T> = None;
+        let mut i = l;
+        for k in (0..self.table.len()).rev() {
+            if i + (1 << k) <= r + 1 {
+                let block = self.table[k][i].clone();
+                result = Some(match result {
+                    None => block,
+                    Some(acc) => O::combine(acc, block),
+                });
+                i += 1 << k;
+            }
+        }
+
+        result.unwrap()
+    }
+}
+
+impl<T, O> SparseTable<T, O>
+where
+    T: Clone,
+    O: Idempotent<T>,
+{
+    /// Aggregates the range [l, r] combining the two (possibly overlapping) blocks of size
+    /// 2^k covering it, where 2^k is the largest power of two not greater than r - l + 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range
+    /// * `r`: the right endpoint of the range
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn idempotent_query(&self, l: usize, r: usize) -> T {
+        assert!(
+            l <= r && r < self.len(),
+            "Sparse Table: range out of bounds"
+        );
+
+        let k = (r - l + 1).ilog2() as usize;
+        O::combine(
+            self.table[k][l].clone(),
+            self.table[k][r + 1 - (1 << k)].clone(),
+        )
+    }
+}
+
+/// Disjoint Sparse Table: at level h the array is split in blocks of size 2^h, and for each
+/// block we store the suffix aggregates of its left half and the prefix aggregates of its right
+/// half. Any range [l, r] with l < r crosses the middle of exactly one block, so it is the
+/// combination of two precomputed values, for every associative operation.
+pub struct DisjointSparseTable<T, O>
+where
+    T: Clone,
+    O: Operation<T>,
+{
+    table: Vec<Vec<T>>,
+    operation: PhantomData<O>,
+}
+
+impl<T, O> DisjointSparseTable<T, O>
+where
+    T: Clone,
+    O: Operation<T>,
+{
+    /// Builds the Disjoint Sparse Table of the given array.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    ///
+    /// returns: DisjointSparseTable<T, O>
+    /// \theta(n log n)
+    pub fn from_vec(a: Vec<T>) -> Self {
+        let n = a.len();
+        let levels = n.next_power_of_two().trailing_zeros() as usize;
+        let mut table = vec![a.clone(); levels + 1];
+
+        for (h, level) in table.iter_mut().enumerate().skip(1) {
+            let half = 1 << (h - 1);
+            for start in (0..n).step_by(1 << h) {
+                let mid = (start + half).min(n);
+                let end = (start + (1 << h)).min(n);
+
+                // suffix aggregates of the left half
+                for i in (start..mid - 1).rev() {
+                    level[i] = O::combine(a[i].clone(), level[i + 1].clone());
+                }
+                // prefix aggregates of the right half
+                for i in mid + 1..end {
+                    level[i] = O::combine(level[i - 1].clone(), a[i].clone());
+                }
+            }
+        }
+
+        Self {
+            table,
+            operation: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table[0].is_empty()
+    }
+
+    /// Aggregates the range [l, r]. The level is given by the highest bit in which l and r
+    /// differ, i.e. the smallest block containing both that has l and r in different halves.
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range
+    /// * `r`: the right endpoint of the range
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn query(&self, l: usize, r: usize) -> T {
+        assert!(
+            l <= r && r < self.len(),
+            "Sparse Table: range out of bounds"
+        );
+
+        if l == r {
+            return self.table[0][l].clone();
+        }
+
+        let h = (usize::BITS - (l ^ r).leading_zeros()) as usize;
+        O::combine(self.table[h][l].clone(), self.table[h][r].clone())
+    }
+}
+
+#[cfg(test)]
+fn brute_force<T: Clone, O: Operation<T>>(a: &[T], l: usize, r: usize) -> T {
+    a[l + 1..=r]
+        .iter()
+        .fold(a[l].clone(), |acc, x| O::combine(acc, x.clone()))
+}
+
+#[cfg(test)]
+fn pseudo_random_vec(n: usize, seed: u64) -> Vec<u64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % 1000
+        })
+        .collect()
+}
+
+#[test]
+pub fn test_sparse_table_idempotent() {
+    use crate::segment_trees::operation::{And, Gcd, Max, Min, Or};
+
+    let a = pseudo_random_vec(37, 42);
+    let min: SparseTable<u64, Min> = SparseTable::from_vec(a.clone());
+    let max: SparseTable<u64, Max> = SparseTable::from_vec(a.clone());
+    let gcd: SparseTable<u64, Gcd> = SparseTable::from_vec(a.clone());
+    let and: SparseTable<u64, And> = SparseTable::from_vec(a.clone());
+    let or: SparseTable<u64, Or> = SparseTable::from_vec(a.clone());
+
+    for l in 0..a.len() {
+        for r in l..a.len() {
+            assert_eq!(min.idempotent_query(l, r), brute_force::<_, Min>(&a, l, r));
+            assert_eq!(max.idempotent_query(l, r), brute_force::<_, Max>(&a, l, r));
+            assert_eq!(gcd.idempotent_query(l, r), brute_force::<_, Gcd>(&a, l, r));
+            assert_eq!(and.idempotent_query(l, r), brute_force::<_, And>(&a, l, r));
+            assert_eq!(or.idempotent_query(l, r), brute_force::<_, Or>(&a, l, r));
+            assert_eq!(min.query(l, r), min.idempotent_query(l, r));
+        }
+    }
+}
+
+#[test]
+pub fn test_sparse_table_non_idempotent() {
+    use crate::segment_trees::operation::{Sum, Xor};
+
+    let a = pseudo_random_vec(50, 7);
+    let sum: SparseTable<u64, Sum> = SparseTable::from_vec(a.clone());
+    let xor: DisjointSparseTable<u64, Xor> = DisjointSparseTable::from_vec(a.clone());
+    let disjoint_sum: DisjointSparseTable<u64, Sum> = DisjointSparseTable::from_vec(a.clone());
+
+    for l in 0..a.len() {
+        for r in l..a.len() {
+            assert_eq!(sum.query(l, r), brute_force::<_, Sum>(&a, l, r));
+            assert_eq!(disjoint_sum.query(l, r), brute_force::<_, Sum>(&a, l, r));
+            assert_eq!(xor.query(l, r), brute_force::<_, Xor>(&a, l, r));
+        }
+    }
+}
+
+#[test]
+pub fn test_sparse_table_preserves_order() {
+    struct Concat;
+    impl Operation<String> for Concat {
+        fn combine(a: String, b: String) -> String {
+            a + &b
+        }
+    }
+
+    let a: Vec<String> = "sparsetable".chars().map(|c| c.to_string()).collect();
+    let sparse_table: SparseTable<String, Concat> = SparseTable::from_vec(a.clone());
+    let disjoint: DisjointSparseTable<String, Concat> = DisjointSparseTable::from_vec(a);
+
+    assert_eq!(sparse_table.query(0, 10), "sparsetable");
+    assert_eq!(sparse_table.query(3, 7), "rseta");
+    assert_eq!(disjoint.query(0, 10), "sparsetable");
+    assert_eq!(disjoint.query(3, 7), "rseta");
+    assert_eq!(disjoint.query(6, 6), "t");
+}
+
+#[test]
+pub fn test_sparse_table_matches_segment_tree() {
+    use crate::segment_trees::operation::Min;
+    use crate::segment_trees::segment_tree::SegmentTree;
+
+    let a: Vec<usize> = pseudo_random_vec(20, 3)
+        .into_iter()
+        .map(|x| x as usize)
+        .collect();
+    let sparse_table: SparseTable<usize, Min> = SparseTable::from_vec(a.clone());
+    let mut segment_tree = SegmentTree::with_operation::<Min>(a.clone(), |a, b| a + b);
+
+    for l in 0..a.len() {
+        for r in l..a.len() {
+            assert_eq!(
+                Some(sparse_table.idempotent_query(l, r)),
+                segment_tree.query(l, r)
+            );
+        }
+    }
+}
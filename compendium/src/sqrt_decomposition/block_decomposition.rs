@@ -0,0 +1,396 @@
+use crate::segment_trees::operation::{Max, Min, Operation, Sum};
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use num_traits::FromPrimitive;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Range};
+
+/// Describes how a range update changes the elements and the aggregates of the operation `O`.
+/// Updates are stored as lazy tags on whole blocks and pushed down to the elements only when a
+/// block is partially covered.
+pub trait LazyUpdate<T, O>
+where
+    O: Operation<T>,
+{
+    type Tag: Clone;
+
+    /// Applies the tag to a single element.
+    fn apply(value: T, tag: &Self::Tag) -> T;
+
+    /// Applies the tag to the aggregate of `len` elements.
+    fn apply_aggregate(aggregate: T, tag: &Self::Tag, len: usize) -> T;
+
+    /// Returns the tag equivalent to applying `old` and then `new`.
+    fn compose(old: Self::Tag, new: &Self::Tag) -> Self::Tag;
+}
+
+/// Adds a value to every element of the range.
+pub struct RangeAdd;
+
+/// Sets every element of the range to a value.
+pub struct RangeAssign;
+
+impl<T> LazyUpdate<T, Sum> for RangeAdd
+where
+    T: Clone + Add<Output = T> + Mul<Output = T> + FromPrimitive,
+{
+    type Tag = T;
+
+    fn apply(value: T, tag: &T) -> T {
+        value + tag.clone()
+    }
+
+    fn apply_aggregate(aggregate: T, tag: &T, len: usize) -> T {
+        aggregate + tag.clone() * T::from_usize(len).unwrap()
+    }
+
+    fn compose(old: T, new: &T) -> T {
+        old + new.clone()
+    }
+}
+
+impl<T> LazyUpdate<T, Min> for RangeAdd
+where
+    T: Clone + Ord + Add<Output = T>,
+{
+    type Tag = T;
+
+    fn apply(value: T, tag: &T) -> T {
+        value + tag.clone()
+    }
+
+    fn apply_aggregate(aggregate: T, tag: &T, _: usize) -> T {
+        aggregate + tag.clone()
+    }
+
+    fn compose(old: T, new: &T) -> T {
+        old + new.clone()
+    }
+}
+
+impl<T> LazyUpdate<T, Max> for RangeAdd
+where
+    T: Clone + Ord + Add<Output = T>,
+{
+    type Tag = T;
+
+    fn apply(value: T, tag: &T) -> T {
+        value + tag.clone()
+    }
+
+    fn apply_aggregate(aggregate: T, tag: &T, _: usize) -> T {
+        aggregate + tag.clone()
+    }
+
+    fn compose(old: T, new: &T) -> T {
+        old + new.clone()
+    }
+}
+
+impl<T> LazyUpdate<T, Sum> for RangeAssign
+where
+    T: Clone + Add<Output = T> + Mul<Output = T> + FromPrimitive,
+{
+    type Tag = T;
+
+    fn apply(_: T, tag: &T) -> T {
+        tag.clone()
+    }
+
+    fn apply_aggregate(_: T, tag: &T, len: usize) -> T {
+        tag.clone() * T::from_usize(len).unwrap()
+    }
+
+    fn compose(_: T, new: &T) -> T {
+        new.clone()
+    }
+}
+
+impl<T> LazyUpdate<T, Min> for RangeAssign
+where
+    T: Clone + Ord,
+{
+    type Tag = T;
+
+    fn apply(_: T, tag: &T) -> T {
+        tag.clone()
+    }
+
+    fn apply_aggregate(_: T, tag: &T, _: usize) -> T {
+        tag.clone()
+    }
+
+    fn compose(_: T, new: &T) -> T {
+        new.clone()
+    }
+}
+
+impl<T> LazyUpdate<T, Max> for RangeAssign
+where
+    T: Clone + Ord,
+{
+    type Tag = T;
+
+    fn apply(_: T, tag: &T) -> T {
+        tag.clone()
+    }
+
+    fn apply_aggregate(_: T, tag: &T, _: usize) -> T {
+        tag.clone()
+    }
+
+    fn compose(_: T, new: &T) -> T {
+        new.clone()
+    }
+}
+
+/// Sqrt Decomposition: the array is split in blocks of size b, each storing the aggregate of
+/// its elements with the operation `O` and a lazy tag with the pending update `U`.
+/// With b = sqrt(n) both range updates and range queries cost O(sqrt(n)).
+/// Ranges are closed [l, r], as in the Segment Tree.
+pub struct SqrtDecomposition<T, O, U>
+where
+    T: Clone,
+    O: Operation<T>,
+    U: LazyUpdate<T, O>,
+{
+    a: Vec<T>,
+    block_size: usize,
+    blocks: Vec<T>,
+    lazy: Vec<Option<U::Tag>>,
+    operation: PhantomData<O>,
+}
+
+impl<T, O, U> SqrtDecomposition<T, O, U>
+where
+    T: Clone,
+    O: Operation<T>,
+    U: LazyUpdate<T, O>,
+{
+    /// Builds the decomposition with blocks of size sqrt(n).
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    ///
+    /// returns: SqrtDecomposition<T, O, U>
+    /// \theta(n)
+    pub fn from_vec(a: Vec<T>) -> Self {
+        let block_size = ((a.len() as f64).sqrt() as usize).max(1);
+        Self::with_block_size(a, block_size)
+    }
+
+    /// Builds the decomposition with blocks of the given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    /// * `block_size`: the size of each block (the last one might be smaller)
+    ///
+    /// returns: SqrtDecomposition<T, O, U>
+    /// \theta(n)
+    pub fn with_block_size(a: Vec<T>, block_size: usize) -> Self {
+        assert!(block_size > 0, "Sqrt Decomposition: empty blocks");
+        let blocks: Vec<T> = a.chunks(block_size).map(Self::aggregate).collect();
+        let lazy = vec![None; blocks.len()];
+        Self {
+            a,
+            block_size,
+            blocks,
+            lazy,
+            operation: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+
+    /// Returns the value at position i.
+    ///
+    /// # Arguments
+    ///
+    /// * `i`: the position in the array
+    ///
+    /// returns: T
+    /// \theta(1)
+    pub fn get(&self, i: usize) -> T {
+        match &self.lazy[i / self.block_size] {
+            Some(tag) => U::apply(self.a[i].clone(), tag),
+            None => self.a[i].clone(),
+        }
+    }
+
+    /// Applies the update to every element in the range [l, r].
+    /// Whole blocks only receive the tag, partially covered blocks are rebuilt.
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range
+    /// * `r`: the right endpoint of the range
+    /// * `tag`: the update
+    ///
+    /// returns: ()
+    /// \theta(b + n / b)
+    pub fn update(&mut self, l: usize, r: usize, tag: U::Tag) {
+        assert!(
+            l <= r && r < self.len(),
+            "Sqrt Decomposition: range out of bounds"
+        );
+
+        let first = l / self.block_size;
+        let last = r / self.block_size;
+
+        if first == last {
+            self.update_partial(first, l, r, &tag);
+            return;
+        }
+
+        self.update_partial(first, l, self.block(first).end - 1, &tag);
+        for b in first + 1..last {
+            let len = self.block(b).len();
+            self.blocks[b] = U::apply_aggregate(self.blocks[b].clone(), &tag, len);
+            self.lazy[b] = Some(match self.lazy[b].take() {
+                None => tag.clone(),
+                Some(old) => U::compose(old, &tag),
+            });
+        }
+        self.update_partial(last, self.block(last).start, r, &tag);
+    }
+
+    /// Aggregates the elements in the range [l, r].
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range
+    /// * `r`: the right endpoint of the range
+    ///
+    /// returns: T
+    /// \theta(b + n / b)
+    pub fn query(&self, l: usize, r: usize) -> T {
+        assert!(
+            l <= r && r < self.len(),
+            "Sqrt Decomposition: range out of bounds"
+        );
+
+        let mut result: Option<T> = None;
+        let mut i = l;
+        while i <= r {
+            let b = i / self.block_size;
+            let block = self.block(b);
+            let value = if i == block.start && block.end <= r + 1 {
+                i = block.end;
+                self.blocks[b].clone()
+            } else {
+                i += 1;
+                self.get(i - 1)
+            };
+            result = Some(match result {
+                None => value,
+                Some(acc) => O::combine(acc, value),
+            });
+        }
+
+        result.unwrap()
+    }
+
+    fn block(&self, b: usize) -> Range<usize> {
+        b * self.block_size..((b + 1) * self.block_size).min(self.len())
+    }
+
+    fn aggregate(block: &[T]) -> T {
+        block[1..]
+            .iter()
+            .fold(block[0].clone(), |acc, x| O::combine(acc, x.clone()))
+    }
+
+    /// Pushes the pending tag of block b down to its elements, applies the update to [l, r]
+    /// and recomputes the aggregate of the block.
+    fn update_partial(&mut self, b: usize, l: usize, r: usize, tag: &U::Tag) {
+        let block = self.block(b);
+        if let Some(pending) = self.lazy[b].take() {
+            for i in block.clone() {
+                self.a[i] = U::apply(self.a[i].clone(), &pending);
+            }
+        }
+        for i in l..=r {
+            self.a[i] = U::apply(self.a[i].clone(), tag);
+        }
+        self.blocks[b] = Self::aggregate(&self.a[block]);
+    }
+}
+
+#[test]
+pub fn test_sqrt_decomposition() {
+    let a = vec![1, 2, 3, 4, 5, 6, 7];
+    let mut sum: SqrtDecomposition<i64, Sum, RangeAdd> = SqrtDecomposition::from_vec(a.clone());
+    let mut min: SqrtDecomposition<i64, Min, RangeAssign> = SqrtDecomposition::from_vec(a);
+
+    assert_eq!(sum.query(0, 6), 28);
+    sum.update(1, 5, -2);
+    assert_eq!(sum.query(0, 6), 18);
+    assert_eq!(sum.query(2, 2), 1);
+    assert_eq!(sum.get(5), 4);
+
+    assert_eq!(min.query(3, 6), 4);
+    min.update(0, 4, 10);
+    assert_eq!(min.query(0, 6), 6);
+    min.update(2, 2, -1);
+    assert_eq!(min.query(0, 6), -1);
+    assert_eq!(min.query(3, 4), 10);
+}
+
+#[test]
+pub fn test_sqrt_decomposition_block_sizes() {
+    let a: Vec<i64> = (0..23).map(|i| (i * 7919) % 31 - 15).collect();
+    for block_size in [1, 2, 5, 23, 100] {
+        let mut max: SqrtDecomposition<i64, Max, RangeAdd> =
+            SqrtDecomposition::with_block_size(a.clone(), block_size);
+        let mut expected = a.clone();
+        for (l, r, v) in [(0, 22, 3), (4, 17, -8), (10, 10, 50), (15, 22, 1)] {
+            max.update(l, r, v);
+            expected[l..=r].iter_mut().for_each(|x| *x += v);
+            for l in 0..a.len() {
+                for r in l..a.len() {
+                    assert_eq!(max.query(l, r), *expected[l..=r].iter().max().unwrap());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+pub fn test_sqrt_decomposition_matches_segment_tree() {
+    use crate::segment_trees::segment_tree::SegmentTree;
+
+    let n = 40;
+    let a: Vec<usize> = (0..n).map(|i| (i * 37) % 11).collect();
+    let sum = |a, b| a + b;
+    let mut segment_tree = SegmentTree::from_vec(a.clone(), sum, sum, sum);
+    let mut sqrt_decomposition: SqrtDecomposition<usize, Sum, RangeAdd> =
+        SqrtDecomposition::from_vec(a);
+
+    let mut rng = Lcg::new(17);
+    for _ in 0..200 {
+        let state = rng.next_u64() as usize;
+        let l = state % n;
+        let r = l + (state / n) % (n - l);
+        match state % 3 {
+            0 => {
+                let v = state % 5 + 1;
+                segment_tree.range_update(l, r, v);
+                sqrt_decomposition.update(l, r, v);
+            }
+            _ => {
+                assert_eq!(
+                    segment_tree.query(l, r),
+                    Some(sqrt_decomposition.query(l, r))
+                );
+            }
+        }
+    }
+}
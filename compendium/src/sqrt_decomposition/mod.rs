@@ -0,0 +1,2 @@
+mod block_decomposition;
+mod sqrt_buckets;
@@ -0,0 +1,160 @@
+#[cfg(test)]
+use crate::utilities::random::Lcg;
+use std::ops::Range;
+
+/// Sqrt Buckets: the array is split in blocks of size b and each block keeps a sorted copy of
+/// its elements. Counting the elements <= k in a range binary searches the whole blocks and
+/// scans the partially covered ones, a point update re-inserts a single value in its block.
+/// Ranges are closed [l, r].
+pub struct SqrtBuckets<T>
+where
+    T: Ord + Copy,
+{
+    a: Vec<T>,
+    block_size: usize,
+    sorted: Vec<Vec<T>>,
+}
+
+impl<T> SqrtBuckets<T>
+where
+    T: Ord + Copy,
+{
+    /// Builds the buckets with blocks of size sqrt(n).
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    ///
+    /// returns: SqrtBuckets<T>
+    /// \theta(n log n)
+    pub fn from_vec(a: Vec<T>) -> Self {
+        let block_size = ((a.len() as f64).sqrt() as usize).max(1);
+        Self::with_block_size(a, block_size)
+    }
+
+    /// Builds the buckets with blocks of the given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the input array
+    /// * `block_size`: the size of each block (the last one might be smaller)
+    ///
+    /// returns: SqrtBuckets<T>
+    /// \theta(n log b)
+    pub fn with_block_size(a: Vec<T>, block_size: usize) -> Self {
+        assert!(block_size > 0, "Sqrt Buckets: empty blocks");
+        let sorted = a
+            .chunks(block_size)
+            .map(|block| {
+                let mut block = block.to_vec();
+                block.sort_unstable();
+                block
+            })
+            .collect();
+        Self {
+            a,
+            block_size,
+            sorted,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.a.is_empty()
+    }
+
+    /// Sets the value at position i to v.
+    ///
+    /// # Arguments
+    ///
+    /// * `i`: the position in the array
+    /// * `v`: the new value
+    ///
+    /// returns: ()
+    /// \theta(b)
+    pub fn update(&mut self, i: usize, v: T) {
+        let block = &mut self.sorted[i / self.block_size];
+        let old = block.binary_search(&self.a[i]).unwrap();
+        block.remove(old);
+        let new = block.partition_point(|&x| x < v);
+        block.insert(new, v);
+        self.a[i] = v;
+    }
+
+    /// Counts the elements in the range [l, r] that are <= k.
+    ///
+    /// # Arguments
+    ///
+    /// * `l`: the left endpoint of the range
+    /// * `r`: the right endpoint of the range
+    /// * `k`: the threshold
+    ///
+    /// returns: usize
+    /// \theta(b + (n / b) log b)
+    pub fn count_less_equal(&self, l: usize, r: usize, k: T) -> usize {
+        assert!(
+            l <= r && r < self.len(),
+            "Sqrt Buckets: range out of bounds"
+        );
+
+        let mut count = 0;
+        let mut i = l;
+        while i <= r {
+            let b = i / self.block_size;
+            let block = self.block(b);
+            if i == block.start && block.end <= r + 1 {
+                count += self.sorted[b].partition_point(|&x| x <= k);
+                i = block.end;
+            } else {
+                count += usize::from(self.a[i] <= k);
+                i += 1;
+            }
+        }
+
+        count
+    }
+
+    fn block(&self, b: usize) -> Range<usize> {
+        b * self.block_size..((b + 1) * self.block_size).min(self.len())
+    }
+}
+
+#[test]
+pub fn test_sqrt_buckets() {
+    let mut buckets = SqrtBuckets::from_vec(vec![5, 1, 4, 2, 3, 9, 7, 8, 6]);
+    assert_eq!(buckets.count_less_equal(0, 8, 5), 5);
+    assert_eq!(buckets.count_less_equal(2, 6, 4), 3);
+    buckets.update(5, 0);
+    assert_eq!(buckets.count_less_equal(2, 6, 4), 4);
+    assert_eq!(buckets.count_less_equal(5, 5, 0), 1);
+    assert_eq!(buckets.count_less_equal(6, 8, 5), 0);
+}
+
+#[test]
+pub fn test_sqrt_buckets_brute_force() {
+    let n = 30;
+    let mut a: Vec<i32> = (0..n).map(|i| ((i * 53) % 17) as i32 - 8).collect();
+    for block_size in [1, 3, 7, 30] {
+        let mut buckets = SqrtBuckets::with_block_size(a.clone(), block_size);
+        let mut rng = Lcg::new(11);
+        for _ in 0..300 {
+            let state = rng.next_u64() as usize;
+            let l = state % n;
+            let r = l + (state / n) % (n - l);
+            let k = (state % 19) as i32 - 9;
+            match state % 4 {
+                0 => {
+                    buckets.update(l, k);
+                    a[l] = k;
+                }
+                _ => {
+                    let expected = a[l..=r].iter().filter(|&&x| x <= k).count();
+                    assert_eq!(buckets.count_less_equal(l, r, k), expected);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+/// Fixed size set of bits packed in u64 words, supporting the word-parallel shifts used by the
+/// bitset dynamic programming algorithms: `or_shifted_left(k)` computes self |= self << k in
+/// \theta(len / 64) instead of \theta(len).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates a bitset of `len` bits, all unset.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "BitSet: index out of bounds");
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize) {
+        assert!(i < self.len, "BitSet: index out of bounds");
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns the indices of the set bits in increasing order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || match word {
+                0 => None,
+                _ => {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(i * 64 + bit)
+                }
+            })
+        })
+    }
+
+    /// Sets bit i + k for every set bit i, dropping the bits shifted past the end.
+    ///
+    /// # Arguments
+    ///
+    /// * `k`: the shift
+    ///
+    /// returns: ()
+    /// \theta(len / 64)
+    pub fn or_shifted_left(&mut self, k: usize) {
+        let (shift_words, shift_bits) = (k / 64, k % 64);
+        // from the last word, so every word is read before being updated
+        for i in (shift_words..self.words.len()).rev() {
+            let mut word = self.words[i - shift_words] << shift_bits;
+            if shift_bits > 0 && i > shift_words {
+                word |= self.words[i - shift_words - 1] >> (64 - shift_bits);
+            }
+            self.words[i] |= word;
+        }
+        self.trim();
+    }
+
+    /// Sets bit i - k for every set bit i >= k.
+    ///
+    /// # Arguments
+    ///
+    /// * `k`: the shift
+    ///
+    /// returns: ()
+    /// \theta(len / 64)
+    pub fn or_shifted_right(&mut self, k: usize) {
+        let (shift_words, shift_bits) = (k / 64, k % 64);
+        let n = self.words.len();
+        // from the first word, so every word is read before being updated
+        for i in 0..n.saturating_sub(shift_words) {
+            let mut word = self.words[i + shift_words] >> shift_bits;
+            if shift_bits > 0 && i + shift_words + 1 < n {
+                word |= self.words[i + shift_words + 1] << (64 - shift_bits);
+            }
+            self.words[i] |= word;
+        }
+    }
+
+    /// Clears the bits of the last word past the end.
+    fn trim(&mut self) {
+        let bits = self.len % 64;
+        if bits > 0 {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << bits) - 1;
+        }
+    }
+}
+
+#[test]
+pub fn test_bitset_shifts() {
+    for len in [1, 63, 64, 65, 200] {
+        for k in [0, 1, 5, 63, 64, 65, 130, 250] {
+            let ones: Vec<usize> = (0..len).filter(|i| i % 7 == 3 || i % 11 == 5).collect();
+            let mut left = BitSet::new(len);
+            let mut right = BitSet::new(len);
+            ones.iter().for_each(|&i| {
+                left.set(i);
+                right.set(i);
+            });
+            left.or_shifted_left(k);
+            right.or_shifted_right(k);
+
+            for i in 0..len {
+                let expected_left = ones.contains(&i) || (i >= k && ones.contains(&(i - k)));
+                let expected_right = ones.contains(&i) || ones.contains(&(i + k));
+                assert_eq!(left.get(i), expected_left);
+                assert_eq!(right.get(i), expected_right);
+            }
+            assert_eq!(left.iter_ones().count(), left.count_ones());
+            assert!(left.iter_ones().all(|i| i < len));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+/// Coordinate compression: maps the distinct values of a collection to the dense ranks
+/// [0, k) preserving their relative order, where k is the number of distinct values.
+/// Useful to index Fenwick and Segment Trees with values from a large or non-integer domain.
+pub struct CoordinateCompressor<T>
+where
+    T: Ord,
+{
+    values: Vec<T>,
+}
+
+impl<T> CoordinateCompressor<T>
+where
+    T: Ord,
+{
+    /// Creates a compressor over the distinct values yielded by the iterator.
+    ///
+    /// # Arguments
+    ///
+    /// * `values`: the values to compress, duplicates are allowed
+    ///
+    /// returns: CoordinateCompressor<T>
+    /// \theta(n log n)
+    pub fn new<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// Returns the number of distinct values, i.e. the size of the compressed domain.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the distinct values in increasing order, the i-th value has rank i.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the rank of a value, or None if the value was not compressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `v`: the value to look up
+    ///
+    /// returns: Option<usize>
+    /// \theta(log k)
+    pub fn rank(&self, v: &T) -> Option<usize> {
+        self.values.binary_search(v).ok()
+    }
+
+    /// Returns the value having the given rank, or None if the rank is out of range.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank`: the rank in [0, k)
+    ///
+    /// returns: Option<&T>
+    /// \theta(1)
+    pub fn value(&self, rank: usize) -> Option<&T> {
+        self.values.get(rank)
+    }
+
+    /// Returns the smallest rank whose value is >= v (k if there is none).
+    /// The value v does not need to be in the compressed domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `v`: the value to search
+    ///
+    /// returns: usize
+    /// \theta(log k)
+    pub fn lower_bound(&self, v: &T) -> usize {
+        self.values.partition_point(|x| x < v)
+    }
+
+    /// Returns the smallest rank whose value is > v (k if there is none).
+    /// The value v does not need to be in the compressed domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `v`: the value to search
+    ///
+    /// returns: usize
+    /// \theta(log k)
+    pub fn upper_bound(&self, v: &T) -> usize {
+        self.values.partition_point(|x| x <= v)
+    }
+
+    /// Maps every element of the slice to its rank.
+    ///
+    /// # Panics
+    /// Panics if an element does not belong to the compressed domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `a`: the values to compress
+    ///
+    /// returns: Vec<usize, Global>
+    /// \theta(n log k)
+    pub fn compress(&self, a: &[T]) -> Vec<usize> {
+        a.iter()
+            .map(|v| {
+                self.rank(v)
+                    .expect("Coordinate Compression: value not in domain")
+            })
+            .collect()
+    }
+}
+
+impl<T> FromIterator<T> for CoordinateCompressor<T>
+where
+    T: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+#[test]
+pub fn test_coordinate_compression() {
+    let a = vec![100, -5, 7, 100, 3];
+    let compressor = CoordinateCompressor::new(a.iter().copied());
+    assert_eq!(compressor.len(), 4);
+    assert_eq!(compressor.values(), &[-5, 3, 7, 100]);
+    assert_eq!(compressor.compress(&a), vec![3, 0, 2, 3, 1]);
+    assert_eq!(compressor.rank(&7), Some(2));
+    assert_eq!(compressor.rank(&8), None);
+    assert_eq!(compressor.value(3), Some(&100));
+    assert_eq!(compressor.value(4), None);
+}
+
+#[test]
+pub fn test_coordinate_compression_bounds() {
+    let compressor: CoordinateCompressor<i64> = vec![10, 20, 20, 30].into_iter().collect();
+    assert_eq!(compressor.lower_bound(&5), 0);
+    assert_eq!(compressor.lower_bound(&20), 1);
+    assert_eq!(compressor.upper_bound(&20), 2);
+    assert_eq!(compressor.lower_bound(&25), 2);
+    assert_eq!(compressor.upper_bound(&30), 3);
+    assert_eq!(compressor.lower_bound(&31), 3);
+}
+
+#[test]
+pub fn test_coordinate_compression_strings() {
+    let words = ["pear", "apple", "fig", "apple"];
+    let compressor = CoordinateCompressor::new(words.iter().map(|s| s.to_string()));
+    assert_eq!(compressor.rank(&"fig".to_string()), Some(1));
+    assert_eq!(compressor.value(0).map(|s| s.as_str()), Some("apple"));
+}
@@ -0,0 +1,288 @@
+use num_traits::{One, Zero};
+
+/// A semiring over the values of type T: an addition with identity `zero` and a multiplication
+/// with identity `one` distributing over it. Semirings are values, so they can carry
+/// parameters such as a modulus.
+pub trait Semiring<T> {
+    fn zero(&self) -> T;
+    fn one(&self) -> T;
+    fn add(&self, a: &T, b: &T) -> T;
+    fn mul(&self, a: &T, b: &T) -> T;
+}
+
+/// The usual (+, *) on numbers.
+pub struct Arithmetic;
+
+/// (+, *) modulo a given modulus, on values in [0, modulus).
+pub struct Modular {
+    pub modulus: u64,
+}
+
+/// (min, +): multiplying matrices composes shortest paths. None is +infinity.
+pub struct MinPlus;
+
+/// (max, +): multiplying matrices composes longest paths. None is -infinity.
+pub struct MaxPlus;
+
+/// (or, and): multiplying matrices composes reachability.
+pub struct Boolean;
+
+impl<T> Semiring<T> for Arithmetic
+where
+    T: Zero + One + Clone,
+{
+    fn zero(&self) -> T {
+        T::zero()
+    }
+
+    fn one(&self) -> T {
+        T::one()
+    }
+
+    fn add(&self, a: &T, b: &T) -> T {
+        a.clone() + b.clone()
+    }
+
+    fn mul(&self, a: &T, b: &T) -> T {
+        a.clone() * b.clone()
+    }
+}
+
+impl Semiring<u64> for Modular {
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn one(&self) -> u64 {
+        1 % self.modulus
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.modulus as u128) as u64
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.modulus as u128) as u64
+    }
+}
+
+impl Semiring<Option<i64>> for MinPlus {
+    fn zero(&self) -> Option<i64> {
+        None
+    }
+
+    fn one(&self) -> Option<i64> {
+        Some(0)
+    }
+
+    fn add(&self, a: &Option<i64>, b: &Option<i64>) -> Option<i64> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(*a.min(b)),
+            _ => a.or(*b),
+        }
+    }
+
+    fn mul(&self, a: &Option<i64>, b: &Option<i64>) -> Option<i64> {
+        Some(a.as_ref()? + b.as_ref()?)
+    }
+}
+
+impl Semiring<Option<i64>> for MaxPlus {
+    fn zero(&self) -> Option<i64> {
+        None
+    }
+
+    fn one(&self) -> Option<i64> {
+        Some(0)
+    }
+
+    fn add(&self, a: &Option<i64>, b: &Option<i64>) -> Option<i64> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(*a.max(b)),
+            _ => a.or(*b),
+        }
+    }
+
+    fn mul(&self, a: &Option<i64>, b: &Option<i64>) -> Option<i64> {
+        Some(a.as_ref()? + b.as_ref()?)
+    }
+}
+
+impl Semiring<bool> for Boolean {
+    fn zero(&self) -> bool {
+        false
+    }
+
+    fn one(&self) -> bool {
+        true
+    }
+
+    fn add(&self, a: &bool, b: &bool) -> bool {
+        *a || *b
+    }
+
+    fn mul(&self, a: &bool, b: &bool) -> bool {
+        *a && *b
+    }
+}
+
+/// Dense matrix stored by rows, with the product and the power taken over a semiring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix<T> {
+    rows: usize,
+    columns: usize,
+    data: Vec<T>,
+}
+
+impl<T> Matrix<T>
+where
+    T: Clone,
+{
+    /// Builds a matrix from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let columns = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == columns),
+            "Matrix: rows of different lengths"
+        );
+        Self {
+            rows: rows.len(),
+            columns,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Returns the n x n identity matrix of the semiring.
+    pub fn identity<S: Semiring<T>>(n: usize, semiring: &S) -> Self {
+        let mut data = vec![semiring.zero(); n * n];
+        for i in 0..n {
+            data[i * n + i] = semiring.one();
+        }
+        Self {
+            rows: n,
+            columns: n,
+            data,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        &self.data[i * self.columns + j]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        self.data[i * self.columns + j] = value;
+    }
+
+    /// Multiplies two matrices over the semiring.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: the right operand, with as many rows as self has columns
+    /// * `semiring`: the semiring of the values
+    ///
+    /// returns: Matrix<T>
+    /// \theta(n * m * k)
+    pub fn mul<S: Semiring<T>>(&self, other: &Self, semiring: &S) -> Self {
+        assert_eq!(self.columns, other.rows, "Matrix: incompatible shapes");
+
+        let mut data = vec![semiring.zero(); self.rows * other.columns];
+        for i in 0..self.rows {
+            for k in 0..self.columns {
+                let a = self.get(i, k);
+                for j in 0..other.columns {
+                    let cell = &mut data[i * other.columns + j];
+                    *cell = semiring.add(cell, &semiring.mul(a, other.get(k, j)));
+                }
+            }
+        }
+
+        Self {
+            rows: self.rows,
+            columns: other.columns,
+            data,
+        }
+    }
+
+    /// Raises a square matrix to the power e over the semiring, by repeated squaring.
+    ///
+    /// # Arguments
+    ///
+    /// * `e`: the exponent
+    /// * `semiring`: the semiring of the values
+    ///
+    /// returns: Matrix<T>
+    /// \theta(n^3 \log e)
+    pub fn pow<S: Semiring<T>>(&self, mut e: u64, semiring: &S) -> Self {
+        assert_eq!(self.rows, self.columns, "Matrix: not a square matrix");
+
+        let mut result = Self::identity(self.rows, semiring);
+        let mut base = self.clone();
+        while e > 0 {
+            if e & 1 == 1 {
+                result = result.mul(&base, semiring);
+            }
+            e >>= 1;
+            if e > 0 {
+                base = base.mul(&base, semiring);
+            }
+        }
+        result
+    }
+}
+
+#[test]
+pub fn test_matrix_arithmetic() {
+    let a = Matrix::from_rows(vec![vec![1_i64, 2], vec![3, 4]]);
+    let b = Matrix::from_rows(vec![vec![0_i64, 1], vec![1, 0]]);
+    assert_eq!(
+        a.mul(&b, &Arithmetic),
+        Matrix::from_rows(vec![vec![2, 1], vec![4, 3]])
+    );
+    assert_eq!(
+        a.pow(3, &Arithmetic),
+        Matrix::from_rows(vec![vec![37, 54], vec![81, 118]])
+    );
+    assert_eq!(a.pow(0, &Arithmetic), Matrix::identity(2, &Arithmetic));
+
+    let column = Matrix::from_rows(vec![vec![5_i64], vec![6]]);
+    let product = a.mul(&column, &Arithmetic);
+    assert_eq!((product.rows(), product.columns()), (2, 1));
+    assert_eq!(*product.get(1, 0), 39);
+}
+
+#[test]
+pub fn test_matrix_semirings() {
+    // weighted graph 0 -> 1 -> 2 -> 0 and 0 -> 2
+    let edges = [(0, 1, 4), (1, 2, -1), (2, 0, 2), (0, 2, 5)];
+    let mut min_plus = Matrix::from_rows(vec![vec![None; 3]; 3]);
+    let mut reachable = Matrix::from_rows(vec![vec![false; 3]; 3]);
+    for &(u, v, w) in edges.iter() {
+        min_plus.set(u, v, Some(w));
+        reachable.set(u, v, true);
+    }
+
+    // shortest walks with exactly two edges
+    let two = min_plus.pow(2, &MinPlus);
+    assert_eq!(*two.get(0, 2), Some(3));
+    assert_eq!(*two.get(0, 0), Some(7));
+    assert_eq!(*two.get(1, 1), None);
+    assert_eq!(*min_plus.pow(2, &MaxPlus).get(0, 0), Some(7));
+
+    assert!(*reachable.pow(3, &Boolean).get(1, 1));
+    assert!(!*reachable.pow(2, &Boolean).get(1, 1));
+
+    let modular = Modular { modulus: 7 };
+    let a = Matrix::from_rows(vec![vec![3_u64, 5], vec![6, 2]]);
+    assert_eq!(
+        a.mul(&a, &modular),
+        Matrix::from_rows(vec![vec![39 % 7, 25 % 7], vec![30 % 7, 34 % 7]])
+    );
+}
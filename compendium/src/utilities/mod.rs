@@ -1,3 +1,9 @@
+pub(crate) mod bitset;
+pub(crate) mod coordinate_compression;
+pub(crate) mod matrix;
+#[cfg(test)]
+pub(crate) mod random;
+
 macro_rules! define_min_max {
     ($type:ty) => {
         impl MinMax for $type {
@@ -0,0 +1,21 @@
+/// Linear congruential generator for reproducible pseudo-random test data.
+pub(crate) struct Lcg {
+    state: u64,
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next value, in [0, 2^31).
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = (self.state * 1103515245 + 12345) % (1 << 31);
+        self.state
+    }
+
+    /// Returns the next value reduced modulo m.
+    pub fn below(&mut self, m: u64) -> u64 {
+        self.next_u64() % m
+    }
+}
@@ -1,29 +1,25 @@
-use handson2::{get_tests, SegmentTree};
+use handson2::{Exercise1, SegmentTree};
 use std::cmp::{max, min};
+use std::process::exit;
 
 fn main() {
-    let n = 10;
-    for i in 0..n {
-        let test = get_tests("data/exercise1", i);
+    let report = harness::run("data/exercise1", |test: &Exercise1| {
         let data = test.get_data();
         let mut segment_tree = SegmentTree::from_vec(data, max, min, min);
-        let expected_outputs = test.get_expected_outputs();
-        let queries = test.get_queries();
 
         let mut results: Vec<i32> = Vec::new();
-        for query in queries {
+        for query in test.get_queries() {
             match query.2 {
                 Some(v) => segment_tree.update_range(query.0, query.1, v),
                 None => results.push(segment_tree.query(query.0, query.1).unwrap()),
             };
         }
+        results
+    })
+    .expect("Exercise 1: can not read the test directory");
 
-        assert!(
-            results
-                .iter()
-                .zip(expected_outputs.iter())
-                .all(|(a, b)| a == b),
-            "Exercise 1: test failed!"
-        );
+    print!("{}", report);
+    if !report.all_passed() {
+        exit(1);
     }
 }
@@ -1,17 +1,9 @@
-use handson2::{get_tests_ex2, SegmentTree};
+use handson2::{Exercise2, SegmentTree};
 use std::cmp::min;
+use std::process::exit;
 
 fn main() {
-    let n = 7;
-    for i in 0..n {
-        let test = get_tests_ex2("data/exercise2", i);
-
-        assert_eq!(
-            test.get_queries().len(),
-            test.get_expected_outputs().len(),
-            "Error in reading test data"
-        );
-
+    let report = harness::run("data/exercise2", |test: &Exercise2| {
         let data = test.get_data();
 
         // Count array
@@ -35,11 +27,8 @@ fn main() {
         let mut segment_tree = SegmentTree::from_vec(&prefix_sum, min, min, min);
         // segment_tree.print();
 
-        let queries = test.get_queries();
-        let expected_outputs = test.get_expected_outputs();
-
         let mut results: Vec<i32> = Vec::new();
-        for query in queries {
+        for query in test.get_queries() {
             if let Some(v) = query.2 {
                 results.push(
                     if segment_tree.query_normal(query.0, query.1, v).is_some() {
@@ -50,13 +39,12 @@ fn main() {
                 )
             };
         }
+        results
+    })
+    .expect("Exercise 2: can not read the test directory");
 
-        assert!(
-            results
-                .iter()
-                .zip(expected_outputs.iter())
-                .all(|(a, b)| a == b),
-            "Exercise 2: test failed!"
-        );
+    print!("{}", report);
+    if !report.all_passed() {
+        exit(1);
     }
 }
@@ -243,136 +243,113 @@ pub fn right_child(index: usize) -> usize {
 
 // Testing
 
-use std::fmt::Debug;
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
+use harness::{diff_sequences, ParseError, TestCase, Tokens};
+
+/// A query of the exercises: (l, r, Some(k)) is an update, or a threshold query in exercise 2,
+/// and (l, r, None) is a max query.
+pub type Query = (usize, usize, Option<i32>);
+
+/// ----------- Exercise 1 -----------
 
 #[derive(Debug)]
-pub struct Test<T> {
-    data: Vec<T>,
-    queries: Vec<(usize, usize, Option<i32>)>,
-    expected_outputs: Vec<i32>,
+pub struct Exercise1 {
+    data: Vec<i32>,
+    queries: Vec<Query>,
 }
 
-impl<T> Test<T> {
-    pub fn get_data(&self) -> &Vec<T> {
+impl Exercise1 {
+    pub fn get_data(&self) -> &Vec<i32> {
         &self.data
     }
 
-    pub fn get_queries(&self) -> &Vec<(usize, usize, Option<i32>)> {
+    pub fn get_queries(&self) -> &Vec<Query> {
         &self.queries
     }
-
-    pub fn get_expected_outputs(&self) -> &Vec<i32> {
-        &self.expected_outputs
-    }
 }
 
-pub fn get_tests(directory: &str, file_number: usize) -> Test<i32> {
-    let input_file_path = format!("{}/input{}.txt", directory, file_number);
-    let output_file_path = format!("{}/output{}.txt", directory, file_number);
-
-    let mut file_iter_input = BufReader::new(File::open(input_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
-
-    let mut file_iter_output = BufReader::new(File::open(output_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
-
-    // Read the first line for n and m
-    let mut binding = file_iter_input.next().unwrap();
-    let mut iter = binding.split_whitespace();
-    let _ = iter.next().unwrap().parse::<usize>().unwrap();
-    let m = iter.next().unwrap().parse::<usize>().unwrap();
-
-    // Read the second line for the array
-    binding = file_iter_input.next().unwrap();
-    iter = binding.split_whitespace();
-    let data = iter
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect::<Vec<i32>>();
-
-    let mut queries = Vec::new();
-    let mut expected_outputs = Vec::new();
-
-    for _ in 0..m {
-        binding = file_iter_input.next().unwrap();
-        iter = binding.split_whitespace();
-
-        // Update query
-        if iter.next().unwrap().parse::<usize>().unwrap() == 0 {
-            let l = iter.next().unwrap().parse::<usize>().unwrap();
-            let r = iter.next().unwrap().parse::<usize>().unwrap();
-            let k = iter.next().unwrap().parse::<i32>().unwrap();
-            queries.push((l, r, Some(k)));
-
-        // Max query
-        } else {
-            let output = file_iter_output.next().unwrap().parse::<i32>().unwrap();
-            let l = iter.next().unwrap().parse::<usize>().unwrap();
-            let r = iter.next().unwrap().parse::<usize>().unwrap();
-            queries.push((l, r, None));
-            expected_outputs.push(output);
+impl TestCase for Exercise1 {
+    // The results of the max queries
+    type Output = Vec<i32>;
+
+    fn parse_input(input: &str) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(input);
+        let n = tokens.read()?;
+        let m = tokens.read()?;
+        let data = tokens.read_many(n)?;
+
+        let mut queries = Vec::with_capacity(m);
+        for _ in 0..m {
+            let kind: usize = tokens.read()?;
+            let l = tokens.read()?;
+            let r = tokens.read()?;
+            match kind {
+                // Update query
+                0 => queries.push((l, r, Some(tokens.read()?))),
+                // Max query
+                1 => queries.push((l, r, None)),
+                _ => return Err(ParseError(format!("unknown query type {}", kind))),
+            }
         }
+        tokens.finish()?;
+
+        Ok(Exercise1 { data, queries })
     }
 
-    Test {
-        data,
-        queries,
-        expected_outputs,
+    fn parse_output(output: &str) -> Result<Vec<i32>, ParseError> {
+        Tokens::new(output).read_rest()
+    }
+
+    fn compare(expected: &Vec<i32>, actual: &Vec<i32>) -> Option<String> {
+        diff_sequences(expected, actual)
     }
 }
 
 /// ----------- Exercise 2 -----------
 
-pub fn get_tests_ex2(directory: &str, file_number: usize) -> Test<(i32, i32)> {
-    let input_file_path = format!("{}/input{}.txt", directory, file_number);
-    let output_file_path = format!("{}/output{}.txt", directory, file_number);
+#[derive(Debug)]
+pub struct Exercise2 {
+    data: Vec<(i32, i32)>,
+    queries: Vec<Query>,
+}
 
-    let mut file_iter_input = BufReader::new(File::open(input_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
+impl Exercise2 {
+    pub fn get_data(&self) -> &Vec<(i32, i32)> {
+        &self.data
+    }
 
-    let mut file_iter_output = BufReader::new(File::open(output_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
+    pub fn get_queries(&self) -> &Vec<Query> {
+        &self.queries
+    }
+}
 
-    // Read the first line for n and m
-    let mut binding = file_iter_input.next().unwrap();
-    let mut iter = binding.split_whitespace();
-    let n = iter.next().unwrap().parse::<usize>().unwrap();
-    let m = iter.next().unwrap().parse::<usize>().unwrap();
+impl TestCase for Exercise2 {
+    // 1 or 0 for every query, whether some position is covered by exactly k segments
+    type Output = Vec<i32>;
 
-    let mut data = Vec::with_capacity(n);
+    fn parse_input(input: &str) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(input);
+        let n = tokens.read()?;
+        let m = tokens.read()?;
 
-    for _ in 0..n {
-        binding = file_iter_input.next().unwrap();
-        iter = binding.split_whitespace();
-        let x = iter.next().unwrap().parse::<i32>().unwrap();
-        let y = iter.next().unwrap().parse::<i32>().unwrap();
-        data.push((x, y));
-    }
+        let mut data = Vec::with_capacity(n);
+        for _ in 0..n {
+            data.push((tokens.read()?, tokens.read()?));
+        }
 
-    let mut queries = Vec::new();
-    let mut expected_outputs = Vec::new();
+        let mut queries = Vec::with_capacity(m);
+        for _ in 0..m {
+            queries.push((tokens.read()?, tokens.read()?, Some(tokens.read()?)));
+        }
+        tokens.finish()?;
 
-    for _ in 0..m {
-        binding = file_iter_input.next().unwrap();
-        iter = binding.split_whitespace();
+        Ok(Exercise2 { data, queries })
+    }
 
-        let output = file_iter_output.next().unwrap().parse::<i32>().unwrap();
-        let l = iter.next().unwrap().parse::<usize>().unwrap();
-        let r = iter.next().unwrap().parse::<usize>().unwrap();
-        let k = iter.next().unwrap().parse::<i32>().unwrap();
-        queries.push((l, r, Some(k)));
-        expected_outputs.push(output);
+    fn parse_output(output: &str) -> Result<Vec<i32>, ParseError> {
+        Tokens::new(output).read_rest()
     }
 
-    Test {
-        data,
-        queries,
-        expected_outputs,
+    fn compare(expected: &Vec<i32>, actual: &Vec<i32>) -> Option<String> {
+        diff_sequences(expected, actual)
     }
 }
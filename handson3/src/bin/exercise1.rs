@@ -1,19 +1,17 @@
-use handson3::{get_tests_ex1, holiday_planning};
+use handson3::{holiday_planning, Exercise1};
+use std::process::exit;
 
 pub fn main() {
-    let n = 5;
-
-    for i in 0..n {
-        let test = get_tests_ex1("data/exercise1", i);
-        let data = test.get_data();
-        let expected_output = test.get_expected_output();
+    let report = harness::run("data/exercise1", |test: &Exercise1| {
 
         // --------- Solver 2 ---------
 
-        let n = data.len();
-        let d = data[0].len();
-        let output = holiday_planning(data, n, d);
-        assert_eq!(output, expected_output, "Test {} failed", i);
+        holiday_planning(test.get_data(), test.get_days())
+    })
+    .expect("Exercise 1: can not read the test directory");
 
+    print!("{}", report);
+    if !report.all_passed() {
+        exit(1);
     }
-}
\ No newline at end of file
+}
@@ -1,18 +1,17 @@
-use handson3::{get_tests_ex2, design_a_course};
+use handson3::{design_a_course, Exercise2};
+use std::process::exit;
 
 pub fn main() {
-    let n = 11;
-
-    for i in 0..n {
-
-        let test = get_tests_ex2("data/exercise2", i);
+    let report = harness::run("data/exercise2", |test: &Exercise2| {
         let data = test.get_data();
-        let expected_output = test.get_expected_output();
-
 
         // --------- Solver 2 ---------
-        let output = design_a_course(data.clone(), data.len());
-        assert_eq!(output, expected_output, "Test {} failed", i)
+        design_a_course(data.clone(), data.len())
+    })
+    .expect("Exercise 2: can not read the test directory");
 
+    print!("{}", report);
+    if !report.all_passed() {
+        exit(1);
     }
-}
\ No newline at end of file
+}
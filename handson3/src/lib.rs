@@ -1,177 +1,139 @@
-pub fn holiday_planning(data: &Vec<Vec<i32>>, n: usize, d: usize) -> i32 {
-
+pub fn holiday_planning(attractions: &[Vec<i64>], days: usize) -> i64 {
+    holiday_plan(attractions, days).0
+}
 
-    // This array contains the cumulative value (reward) of each attraction in each city
-    // Again here, the first element of the array is 0, meaning that we can decide not to include it in the trip
-    let values: Vec<_> = data
+/// Plans the holiday maximizing the number of visited attractions.
+///
+/// # Arguments
+///
+/// * `attractions`: the attractions of every city, the ones of its j-th day first
+/// * `days`: the length of the holiday
+///
+/// returns: (i64, Vec<usize>), the visited attractions and the days spent in every city,
+/// 0 meaning the city is skipped
+pub fn holiday_plan(attractions: &[Vec<i64>], days: usize) -> (i64, Vec<usize>) {
+
+    // Spending j days in a city means visiting its first j attractions, so every city is a
+    // group of the knapsack whose choices weigh j days and are worth the sum of the first j
+    // attractions. Not visiting the city is skipping the group
+    let groups: Vec<Vec<(usize, i64)>> = attractions
         .iter()
-        .map(|attractions| {
-            let mut result = vec![0; d+1];
-            attractions
-                .iter()
+        .map(|city| {
+            city.iter()
+                .take(days)
+                .scan(0, |reward, attraction| {
+                    *reward += attraction;
+                    Some(*reward)
+                })
                 .enumerate()
-                .for_each(|(i, attraction)| {
-                    result[i + 1] = result[i] + attraction;
-                });
-            result
+                .map(|(j, reward)| (j + 1, reward))
+                .collect()
         })
         .collect();
 
-    // Dynamic programming table
-    let mut table = vec![vec![0; d + 1]; n + 1];
-
-    // For every city
-    for i in 1..=n {
-
-        // For every attraction in the city, including the possibility of not visiting it
-        for j in 0..=d {
-
-            let current_value = values[i - 1][j];
-
-            // Recursion formula, choose the best value among all candidates in the same row
-            // Only if it fits (start from current_weight)
-            for k in j..=d {
-                table[i][k] = max(table[i][k], table[i - 1][k - j] + current_value);
-            }
-        }
-
-    }
-
-    // The result is the bottom right element of the table
-    table[n][d]
+    // The chosen choice of every city is its number of days minus one
+    let (reward, choices) = grouped_knapsack(&groups, days);
+    let days_per_city = choices
+        .into_iter()
+        .map(|choice| choice.map_or(0, |c| c + 1))
+        .collect();
 
+    (reward, days_per_city)
 }
 
-
 pub fn design_a_course(data: Vec<(i32, i32)>, n: usize) -> i32 {
+    design_a_course_topics(&data[..n]).len() as i32
+}
 
-    let mut table = vec![1; n];
-
-    // Sort topics based on the first element of the couple (beauty in this case)
-    let mut sorted_topics = data.clone();
-    sorted_topics.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)));
 
-    // Start from second element because the LIS = 1 counting the first element only
-    for i in 1..n {
-        for j in 0..i {
-            // Contribution only if difficulty is greater than the other element's difficulty
-            if sorted_topics[i].1 > sorted_topics[j].1 {
-                table[i] = table[i].max(table[j] + 1);
-            }
-        }
-    }
+pub fn design_a_course_topics(topics: &[(i32, i32)]) -> Vec<usize> {
 
-    // The result is the maximum of the dynamic programming table
-    table.into_iter().max().unwrap_or(0)
+    // A course is a chain of topics strictly increasing in both beauty and difficulty,
+    // i.e. a chain under strict dominance of the points (beauty, difficulty)
+    longest_dominance_chain(topics)
 }
 
 
 
 // -------- Testing --------
 
-use std::cmp::max;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use compendium::dynamic_programming::knapsack::grouped_knapsack;
+use compendium::dynamic_programming::subsequences::longest_dominance_chain;
+use harness::{ParseError, TestCase, Tokens};
+
+
+/// ----------- Exercise 1 -----------
 
 #[derive(Debug)]
-pub struct Test<T> {
-    data: Vec<T>,
-    expected_output: i32,
+pub struct Exercise1 {
+    // The attractions of every city, one per day
+    data: Vec<Vec<i64>>,
+    days: usize,
 }
 
-impl<T> Test<T> {
-    pub fn get_data(&self) -> &Vec<T> {
+impl Exercise1 {
+    pub fn get_data(&self) -> &Vec<Vec<i64>> {
         &self.data
     }
 
-    pub fn get_expected_output(&self) -> i32 {
-        self.expected_output
+    pub fn get_days(&self) -> usize {
+        self.days
     }
 }
 
+impl TestCase for Exercise1 {
+    type Output = i64;
 
-/// ----------- Exercise 1 -----------
+    fn parse_input(input: &str) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(input);
+        let n = tokens.read()?;
+        let days = tokens.read()?;
 
-pub fn get_tests_ex1(directory: &str, file_number: usize) -> Test<Vec<i32>> {
-    let input_file_path = format!("{}/input{}.txt", directory, file_number);
-    let output_file_path = format!("{}/output{}.txt", directory, file_number);
-
-    let mut file_iter_input = BufReader::new(File::open(input_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
-
-    let mut file_iter_output = BufReader::new(File::open(output_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
-
-    // Read the first line for n and m
-    let mut binding = file_iter_input.next().unwrap();
-    let mut iter = binding.split_whitespace();
-    let n = iter.next().unwrap().parse::<usize>().unwrap();
-    let d = iter.next().unwrap().parse::<usize>().unwrap();
-
-
-    let mut data = Vec::with_capacity(n);
-    let mut attraction = Vec::with_capacity(d);
-
-    for _ in 0..n {
-        binding = file_iter_input.next().unwrap();
-        iter = binding.split_whitespace();
-        for _ in 0..d {
-            let num_activities = iter.next().unwrap().parse::<i32>().unwrap();
-            attraction.push(num_activities);
+        let mut data = Vec::with_capacity(n);
+        for _ in 0..n {
+            data.push(tokens.read_many(days)?);
         }
-        data.push(attraction.clone());
-        attraction.clear();
-    }
-
-    binding = file_iter_output.next().unwrap();
-    iter = binding.split_whitespace();
-    let expected_output = iter.next().unwrap().parse::<i32>().unwrap();
+        tokens.finish()?;
 
+        Ok(Exercise1 { data, days })
+    }
 
-    Test {
-        data,
-        expected_output
+    fn parse_output(output: &str) -> Result<i64, ParseError> {
+        Tokens::new(output).read()
     }
 }
 
-/// ----------- Exercise 1 -----------
+/// ----------- Exercise 2 -----------
 
-pub fn get_tests_ex2(directory: &str, file_number: usize) -> Test<(i32, i32)> {
-    let input_file_path = format!("{}/input{}.txt", directory, file_number);
-    let output_file_path = format!("{}/output{}.txt", directory, file_number);
-
-    let mut file_iter_input = BufReader::new(File::open(input_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
+#[derive(Debug)]
+pub struct Exercise2 {
+    // The (beauty, difficulty) of every topic
+    data: Vec<(i32, i32)>,
+}
 
-    let mut file_iter_output = BufReader::new(File::open(output_file_path).unwrap())
-        .lines()
-        .map(|x| x.unwrap());
+impl Exercise2 {
+    pub fn get_data(&self) -> &Vec<(i32, i32)> {
+        &self.data
+    }
+}
 
-    // Read the first line for n
-    let mut binding = file_iter_input.next().unwrap();
-    let mut iter = binding.split_whitespace();
-    let n = iter.next().unwrap().parse::<usize>().unwrap();
+impl TestCase for Exercise2 {
+    type Output = i32;
 
-    let mut data = Vec::with_capacity(n);
+    fn parse_input(input: &str) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(input);
+        let n = tokens.read()?;
 
-    for _ in 0..n {
-        binding = file_iter_input.next().unwrap();
-        iter = binding.split_whitespace();
-        let x = iter.next().unwrap().parse::<i32>().unwrap();
-        let y = iter.next().unwrap().parse::<i32>().unwrap();
-        data.push((x, y));
+        let mut data = Vec::with_capacity(n);
+        for _ in 0..n {
+            data.push((tokens.read()?, tokens.read()?));
+        }
+        tokens.finish()?;
 
+        Ok(Exercise2 { data })
     }
 
-    binding = file_iter_output.next().unwrap();
-    iter = binding.split_whitespace();
-    let expected_output = iter.next().unwrap().parse::<i32>().unwrap();
-
-    Test {
-        data,
-        expected_output,
+    fn parse_output(output: &str) -> Result<i32, ParseError> {
+        Tokens::new(output).read()
     }
-}
\ No newline at end of file
+}
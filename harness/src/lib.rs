@@ -0,0 +1,436 @@
+use std::fmt::{self, Debug, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::{FromStr, SplitWhitespace};
+
+/// An error met while reading the content of a test file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(pub String);
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The whitespace separated tokens of a file, parsed one at a time. Line breaks are not
+/// significant, as in every format of the hands-on data.
+pub struct Tokens<'a> {
+    tokens: SplitWhitespace<'a>,
+    read: usize,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(content: &'a str) -> Self {
+        Self {
+            tokens: content.split_whitespace(),
+            read: 0,
+        }
+    }
+
+    /// Parses the next token.
+    ///
+    /// returns: Result<T, ParseError>, an error if the tokens are over or the token is invalid
+    pub fn read<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        self.read += 1;
+        let token = self
+            .tokens
+            .next()
+            .ok_or_else(|| ParseError(format!("token {}: unexpected end of file", self.read)))?;
+        token.parse().map_err(|_| {
+            ParseError(format!(
+                "token {}: {:?} is not a valid {}",
+                self.read,
+                token,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+
+    /// Parses the next count tokens.
+    pub fn read_many<T: FromStr>(&mut self, count: usize) -> Result<Vec<T>, ParseError> {
+        (0..count).map(|_| self.read()).collect()
+    }
+
+    /// Parses all the remaining tokens.
+    pub fn read_rest<T: FromStr>(&mut self) -> Result<Vec<T>, ParseError> {
+        let mut values = Vec::new();
+        while self.tokens.clone().next().is_some() {
+            values.push(self.read()?);
+        }
+        Ok(values)
+    }
+
+    /// Fails if some tokens are left, which usually means the counts of the header are wrong.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        match self.tokens.next() {
+            None => Ok(()),
+            Some(token) => Err(ParseError(format!(
+                "token {}: unexpected {:?} after the end of the data",
+                self.read + 1,
+                token
+            ))),
+        }
+    }
+}
+
+/// A test case of an exercise: its input, parsed from inputN.txt, is handed to the solver, and
+/// the output of the solver is compared with the expected one, parsed from outputN.txt.
+pub trait TestCase: Sized {
+    type Output: PartialEq + Debug;
+
+    /// Parses the content of the input file.
+    fn parse_input(input: &str) -> Result<Self, ParseError>;
+
+    /// Parses the content of the expected output file.
+    fn parse_output(output: &str) -> Result<Self::Output, ParseError>;
+
+    /// Compares the expected output with the one of the solver.
+    ///
+    /// returns: Option<String>, None if they match, otherwise a description of the differences
+    fn compare(expected: &Self::Output, actual: &Self::Output) -> Option<String> {
+        (expected != actual).then(|| format!("expected {:?}, found {:?}", expected, actual))
+    }
+}
+
+/// The maximum number of differences listed by `diff_sequences`.
+const MAX_DIFFERENCES: usize = 5;
+
+/// Describes the differences between two sequences of outputs, one per query: the first
+/// mismatching positions and the difference in length.
+///
+/// # Arguments
+///
+/// * `expected`: the expected outputs
+/// * `actual`: the outputs of the solver
+///
+/// returns: Option<String>, None if the sequences are equal
+/// \theta(n)
+pub fn diff_sequences<T: PartialEq + Debug>(expected: &[T], actual: &[T]) -> Option<String> {
+    let mismatches: Vec<usize> = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter(|(_, (e, a))| e != a)
+        .map(|(i, _)| i)
+        .collect();
+    if mismatches.is_empty() && expected.len() == actual.len() {
+        return None;
+    }
+
+    let mut lines: Vec<String> = mismatches
+        .iter()
+        .take(MAX_DIFFERENCES)
+        .map(|&i| {
+            format!(
+                "output {}: expected {:?}, found {:?}",
+                i, expected[i], actual[i]
+            )
+        })
+        .collect();
+    if mismatches.len() > MAX_DIFFERENCES {
+        lines.push(format!(
+            "... and {} more mismatching outputs",
+            mismatches.len() - MAX_DIFFERENCES
+        ));
+    }
+    if expected.len() != actual.len() {
+        lines.push(format!(
+            "expected {} outputs, found {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+    Some(lines.join("\n"))
+}
+
+/// The files of a test case in a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseFiles {
+    pub number: usize,
+    pub input: PathBuf,
+    pub output: PathBuf,
+}
+
+/// Finds the test cases of a directory, i.e. its inputN.txt files, each paired with the
+/// outputN.txt file of the same N, sorted by N. The output files are not required to exist
+/// here, a missing one makes its case fail when run.
+///
+/// # Arguments
+///
+/// * `directory`: the directory of the test files
+///
+/// returns: io::Result<Vec<CaseFiles>>
+pub fn discover(directory: impl AsRef<Path>) -> io::Result<Vec<CaseFiles>> {
+    let directory = directory.as_ref();
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let name = entry?.file_name();
+        let number = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("input")?.strip_suffix(".txt"))
+            .filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|n| n.parse().ok());
+        if let Some(number) = number {
+            cases.push(CaseFiles {
+                number,
+                input: directory.join(format!("input{}.txt", number)),
+                output: directory.join(format!("output{}.txt", number)),
+            });
+        }
+    }
+    cases.sort_by_key(|case| case.number);
+    Ok(cases)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// The output of the solver differs from the expected one, with the differences.
+    Failed(String),
+    /// The test files could not be read or parsed.
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseReport {
+    pub number: usize,
+    pub outcome: Outcome,
+}
+
+/// The outcome of every test case of a directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub name: String,
+    pub cases: Vec<CaseReport>,
+}
+
+impl Report {
+    pub fn passed(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.outcome == Outcome::Passed)
+            .count()
+    }
+
+    /// Whether there is at least one case and every case passed, so that a wrong directory
+    /// does not go unnoticed.
+    pub fn all_passed(&self) -> bool {
+        !self.cases.is_empty() && self.passed() == self.cases.len()
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {}/{} cases passed",
+            self.name,
+            self.passed(),
+            self.cases.len()
+        )?;
+        for case in &self.cases {
+            let (status, details) = match &case.outcome {
+                Outcome::Passed => continue,
+                Outcome::Failed(diff) => ("failed", diff),
+                Outcome::Error(message) => ("error", message),
+            };
+            writeln!(f, "  case {}: {}", case.number, status)?;
+            for line in details.lines() {
+                writeln!(f, "    {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs a single test case.
+fn run_case<T, F>(files: &CaseFiles, solve: &mut F) -> Outcome
+where
+    T: TestCase,
+    F: FnMut(&T) -> T::Output,
+{
+    let read =
+        |path: &Path| fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e));
+    let parsed = read(&files.input)
+        .and_then(|input| {
+            T::parse_input(&input).map_err(|e| format!("{}: {}", files.input.display(), e))
+        })
+        .and_then(|case| {
+            let output = read(&files.output)?;
+            let expected = T::parse_output(&output)
+                .map_err(|e| format!("{}: {}", files.output.display(), e))?;
+            Ok((case, expected))
+        });
+
+    match parsed {
+        Err(message) => Outcome::Error(message),
+        Ok((case, expected)) => match T::compare(&expected, &solve(&case)) {
+            None => Outcome::Passed,
+            Some(diff) => Outcome::Failed(diff),
+        },
+    }
+}
+
+/// Runs the solver on every test case of a directory, see `discover`.
+///
+/// # Arguments
+///
+/// * `directory`: the directory of the test files
+/// * `solve`: the solver, returning the output of a test case
+///
+/// returns: io::Result<Report>, an error only if the directory can not be listed
+pub fn run<T, F>(directory: impl AsRef<Path>, mut solve: F) -> io::Result<Report>
+where
+    T: TestCase,
+    F: FnMut(&T) -> T::Output,
+{
+    let directory = directory.as_ref();
+    let cases = discover(directory)?
+        .iter()
+        .map(|files| CaseReport {
+            number: files.number,
+            outcome: run_case(files, &mut solve),
+        })
+        .collect();
+    Ok(Report {
+        name: directory.display().to_string(),
+        cases,
+    })
+}
+
+/// A sum of numbers, for the tests.
+#[cfg(test)]
+struct Sum(Vec<i64>);
+
+#[cfg(test)]
+impl TestCase for Sum {
+    type Output = i64;
+
+    fn parse_input(input: &str) -> Result<Self, ParseError> {
+        let mut tokens = Tokens::new(input);
+        let n = tokens.read()?;
+        let values = tokens.read_many(n)?;
+        tokens.finish()?;
+        Ok(Sum(values))
+    }
+
+    fn parse_output(output: &str) -> Result<i64, ParseError> {
+        Tokens::new(output).read()
+    }
+}
+
+/// A fresh directory with the given files, for the tests.
+#[cfg(test)]
+fn test_directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("harness-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    for (file, content) in files {
+        fs::write(directory.join(file), content).unwrap();
+    }
+    directory
+}
+
+#[test]
+pub fn test_tokens() {
+    let mut tokens = Tokens::new("3 2\n 1 -4\n\n7 x");
+    assert_eq!(tokens.read::<usize>(), Ok(3));
+    assert_eq!(tokens.read_many::<i32>(3), Ok(vec![2, 1, -4]));
+    assert_eq!(tokens.read::<u8>(), Ok(7));
+    assert_eq!(
+        tokens.read::<i32>(),
+        Err(ParseError("token 6: \"x\" is not a valid i32".to_string()))
+    );
+    assert_eq!(
+        tokens.read::<i32>(),
+        Err(ParseError("token 7: unexpected end of file".to_string()))
+    );
+
+    assert_eq!(Tokens::new("1 2 3\n").read_rest::<u32>(), Ok(vec![1, 2, 3]));
+    assert_eq!(Tokens::new("").read_rest::<u32>(), Ok(vec![]));
+    let mut tokens = Tokens::new("1 2");
+    tokens.read::<u32>().unwrap();
+    assert!(tokens.finish().is_err());
+}
+
+#[test]
+pub fn test_diff_sequences() {
+    assert_eq!(diff_sequences(&[1, 2, 3], &[1, 2, 3]), None);
+    assert_eq!(diff_sequences::<i32>(&[], &[]), None);
+    assert_eq!(
+        diff_sequences(&[1, 2, 3], &[1, 5, 3]),
+        Some("output 1: expected 2, found 5".to_string())
+    );
+    assert_eq!(
+        diff_sequences(&[1, 2, 3], &[1, 2]),
+        Some("expected 3 outputs, found 2".to_string())
+    );
+
+    let expected: Vec<i32> = (0..10).collect();
+    let diff = diff_sequences(&expected, &[-1; 8]).unwrap();
+    assert_eq!(diff.lines().count(), MAX_DIFFERENCES + 2);
+    assert!(diff.contains("... and 3 more mismatching outputs"));
+}
+
+#[test]
+pub fn test_run() {
+    let directory = test_directory(
+        "run",
+        &[
+            ("input0.txt", "3\n1 2 3\n"),
+            ("output0.txt", "6\n"),
+            ("input10.txt", "2\n5 5\n"),
+            ("output10.txt", "11\n"),
+            ("input2.txt", "2\n1\n"),
+            ("output2.txt", "1\n"),
+            ("input3.txt", "1\n4\n"),
+            ("notes.txt", "not a case"),
+            ("input.txt", "not a case either"),
+        ],
+    );
+
+    let numbers: Vec<usize> = discover(&directory)
+        .unwrap()
+        .iter()
+        .map(|files| files.number)
+        .collect();
+    assert_eq!(numbers, vec![0, 2, 3, 10]);
+
+    let mut solved = 0;
+    let report = run(&directory, |case: &Sum| {
+        solved += 1;
+        case.0.iter().sum()
+    })
+    .unwrap();
+    // the solver does not run on the cases whose files are broken
+    assert_eq!(solved, 2);
+    assert_eq!(report.passed(), 1);
+    assert!(!report.all_passed());
+
+    let outcomes: Vec<&Outcome> = report.cases.iter().map(|case| &case.outcome).collect();
+    assert_eq!(outcomes[0], &Outcome::Passed);
+    assert!(matches!(outcomes[1], Outcome::Error(e) if e.contains("end of file")));
+    assert!(matches!(outcomes[2], Outcome::Error(e) if e.contains("output3.txt")));
+    assert_eq!(
+        outcomes[3],
+        &Outcome::Failed("expected 11, found 10".to_string())
+    );
+
+    let summary = report.to_string();
+    assert!(summary.contains(": 1/4 cases passed"));
+    assert!(summary.contains("  case 10: failed\n    expected 11, found 10\n"));
+    assert!(!summary.contains("case 0:"));
+
+    let empty = test_directory("empty", &[]);
+    assert!(!run(&empty, |case: &Sum| case.0.len() as i64)
+        .unwrap()
+        .all_passed());
+    assert!(run(empty.join("missing"), |case: &Sum| case.0.len() as i64).is_err());
+
+    fs::remove_dir_all(&directory).unwrap();
+    fs::remove_dir_all(&empty).unwrap();
+}